@@ -0,0 +1,93 @@
+use crate::solution::Part;
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+use std::path::Path;
+
+/// Expected answers keyed by day, part and lowercase input name.
+#[derive(Default)]
+pub struct Answers {
+    expected: BTreeMap<(u8, Part, String), String>,
+}
+
+impl Answers {
+    pub fn get(&self, day: u8, part: Part, input: &str) -> Option<&str> {
+        self.expected
+            .get(&(day, part, input.to_lowercase()))
+            .map(String::as_str)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (&(u8, Part, String), &String)> {
+        self.expected.iter()
+    }
+}
+
+pub fn read_answers(path: &Path) -> Result<Answers, String> {
+    let text = read_to_string(path)
+        .map_err(|err| format!("Failed to read file {}: {err}", path.display()))?;
+
+    parse_answers(&text).map_err(|err| format!("{}: {err}", path.display()))
+}
+
+/// Reads the answers when the file exists, so running days does not depend on having one.
+pub fn read_answers_if_present(path: &Path) -> Result<Answers, String> {
+    if path.exists() {
+        read_answers(path)
+    } else {
+        Ok(Answers::default())
+    }
+}
+
+fn parse_answers(text: &str) -> Result<Answers, String> {
+    let mut expected = BTreeMap::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line_number = index + 1;
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [day, part, input, answer] = fields[..] else {
+            return Err(format!(
+                "line {line_number}: expected `<day> <part> <input> <answer>`, found '{line}'"
+            ));
+        };
+
+        let day: u8 = day
+            .parse()
+            .map_err(|_| format!("line {line_number}: invalid day '{day}'"))?;
+        let part = match part {
+            "1" => Part::One,
+            "2" => Part::Two,
+            other => return Err(format!("line {line_number}: invalid part '{other}'")),
+        };
+
+        let key = (day, part, input.to_lowercase());
+        if expected.insert(key, answer.to_owned()).is_some() {
+            return Err(format!(
+                "line {line_number}: duplicate answer for day {day} part {part} {input}"
+            ));
+        }
+    }
+
+    Ok(Answers { expected })
+}
+
+#[test]
+fn test_parse_answers() {
+    let answers = parse_answers("# comment\n\n1 1 example 3\n1 2 Puzzle 1234\n").unwrap();
+
+    assert_eq!(answers.get(1, Part::One, "Example"), Some("3"));
+    assert_eq!(answers.get(1, Part::Two, "puzzle"), Some("1234"));
+    assert_eq!(answers.get(1, Part::Two, "example"), None);
+    assert_eq!(answers.entries().count(), 2);
+}
+
+#[test]
+fn test_parse_invalid_answers() {
+    assert!(parse_answers("1 1 example").is_err());
+    assert!(parse_answers("x 1 example 3").is_err());
+    assert!(parse_answers("1 3 example 3").is_err());
+    assert!(parse_answers("1 1 example 3\n1 1 example 4").is_err());
+}
@@ -0,0 +1,234 @@
+use crate::json::{Json, parse};
+use crate::report::BenchmarkRow;
+use crate::solution::{Part, Phase};
+use std::fs::{read_to_string, write};
+use std::path::Path;
+use std::time::Duration;
+
+/// The median time of one phase against one input, as recorded in a baseline file.
+#[derive(Debug, PartialEq)]
+pub struct BaselineEntry {
+    pub day: u8,
+    pub phase: Phase,
+    pub input: String,
+    pub median: Duration,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Regressed,
+    Improved,
+    Unchanged,
+    New,
+}
+
+pub struct Comparison {
+    pub day: u8,
+    pub phase: Phase,
+    pub input: String,
+    pub baseline: Option<Duration>,
+    pub current: Duration,
+    /// The change relative to the baseline, in percent.
+    pub change: Option<f64>,
+    pub verdict: Verdict,
+}
+
+pub fn save_baseline(path: &Path, rows: &[BenchmarkRow]) -> Result<(), String> {
+    let benchmarks = rows
+        .iter()
+        .map(|row| {
+            let statistics = &row.statistics;
+            Json::Object(vec![
+                ("day".to_owned(), Json::Number(row.day.into())),
+                ("phase".to_owned(), Json::String(row.phase.to_string())),
+                ("input".to_owned(), Json::String(row.input.clone())),
+                (
+                    "iterations".to_owned(),
+                    Json::Number(statistics.iterations as f64),
+                ),
+                ("min_ns".to_owned(), nanoseconds(statistics.min)),
+                ("median_ns".to_owned(), nanoseconds(statistics.median)),
+                ("mean_ns".to_owned(), nanoseconds(statistics.mean)),
+                ("p95_ns".to_owned(), nanoseconds(statistics.p95)),
+                ("stddev_ns".to_owned(), nanoseconds(statistics.stddev)),
+            ])
+        })
+        .collect();
+
+    let document = Json::Object(vec![("benchmarks".to_owned(), Json::Array(benchmarks))]);
+
+    write(path, document.to_string_pretty() + "\n")
+        .map_err(|err| format!("Failed to write baseline {}: {err}", path.display()))
+}
+
+fn nanoseconds(duration: Duration) -> Json {
+    Json::Number(duration.as_nanos() as f64)
+}
+
+pub fn read_baseline(path: &Path) -> Result<Vec<BaselineEntry>, String> {
+    let text = read_to_string(path)
+        .map_err(|err| format!("Failed to read baseline {}: {err}", path.display()))?;
+
+    parse_baseline(&text).map_err(|err| format!("Invalid baseline {}: {err}", path.display()))
+}
+
+fn parse_baseline(text: &str) -> Result<Vec<BaselineEntry>, String> {
+    let document = parse(text)?;
+    let benchmarks = document
+        .get("benchmarks")
+        .and_then(Json::as_array)
+        .ok_or("Missing `benchmarks` array")?;
+
+    benchmarks
+        .iter()
+        .enumerate()
+        .map(|(index, benchmark)| {
+            let number = |field: &str| {
+                benchmark
+                    .get(field)
+                    .and_then(Json::as_f64)
+                    .ok_or_else(|| format!("Benchmark {index} is missing number `{field}`"))
+            };
+
+            let string = |field: &str| {
+                benchmark
+                    .get(field)
+                    .and_then(Json::as_str)
+                    .ok_or_else(|| format!("Benchmark {index} is missing string `{field}`"))
+            };
+
+            let phase = match string("phase")? {
+                "parse" => Phase::Parse,
+                "part-1" => Phase::Solve(Part::One),
+                "part-2" => Phase::Solve(Part::Two),
+                other => return Err(format!("Benchmark {index} has invalid phase '{other}'")),
+            };
+
+            Ok(BaselineEntry {
+                day: number("day")? as u8,
+                phase,
+                input: string("input")?.to_owned(),
+                median: Duration::from_nanos(number("median_ns")? as u64),
+            })
+        })
+        .collect()
+}
+
+/// Compares the median of every benchmark against the baseline. A change of more than
+/// `threshold_percent` in either direction counts as a regression or improvement.
+pub fn compare(
+    baseline: &[BaselineEntry],
+    rows: &[BenchmarkRow],
+    threshold_percent: f64,
+) -> Vec<Comparison> {
+    rows.iter()
+        .map(|row| {
+            let current = row.statistics.median;
+            let baseline = baseline
+                .iter()
+                .find(|entry| {
+                    entry.day == row.day && entry.phase == row.phase && entry.input == row.input
+                })
+                .map(|entry| entry.median);
+
+            let change = baseline.map(|baseline| {
+                let baseline = baseline.as_secs_f64().max(f64::MIN_POSITIVE);
+                (current.as_secs_f64() - baseline) / baseline * 100.0
+            });
+
+            let verdict = match change {
+                None => Verdict::New,
+                Some(change) if change > threshold_percent => Verdict::Regressed,
+                Some(change) if change < -threshold_percent => Verdict::Improved,
+                Some(_) => Verdict::Unchanged,
+            };
+
+            Comparison {
+                day: row.day,
+                phase: row.phase,
+                input: row.input.clone(),
+                baseline,
+                current,
+                change,
+                verdict,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+fn benchmark_row(day: u8, phase: Phase, median_micros: u64) -> BenchmarkRow {
+    use crate::stopwatch::Statistics;
+
+    BenchmarkRow {
+        day,
+        phase,
+        input: "Puzzle".to_owned(),
+        answer: "42".to_owned(),
+        statistics: Statistics::from_samples(&[Duration::from_micros(median_micros)]),
+    }
+}
+
+#[test]
+fn test_baseline_round_trip() {
+    let path = std::env::temp_dir().join(format!("aoc-2025-baseline-{}.json", std::process::id()));
+
+    save_baseline(&path, &[benchmark_row(7, Phase::Solve(Part::Two), 1500)]).unwrap();
+    let baseline = read_baseline(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        baseline,
+        vec![BaselineEntry {
+            day: 7,
+            phase: Phase::Solve(Part::Two),
+            input: "Puzzle".to_owned(),
+            median: Duration::from_micros(1500),
+        }]
+    );
+}
+
+#[test]
+fn test_compare_against_baseline() {
+    let baseline = vec![
+        BaselineEntry {
+            day: 7,
+            phase: Phase::Parse,
+            input: "Puzzle".to_owned(),
+            median: Duration::from_micros(100),
+        },
+        BaselineEntry {
+            day: 7,
+            phase: Phase::Solve(Part::One),
+            input: "Puzzle".to_owned(),
+            median: Duration::from_micros(100),
+        },
+        BaselineEntry {
+            day: 8,
+            phase: Phase::Solve(Part::One),
+            input: "Puzzle".to_owned(),
+            median: Duration::from_micros(100),
+        },
+    ];
+    let rows = vec![
+        benchmark_row(7, Phase::Parse, 105),
+        benchmark_row(7, Phase::Solve(Part::One), 150),
+        benchmark_row(8, Phase::Solve(Part::One), 50),
+        benchmark_row(10, Phase::Solve(Part::Two), 50),
+    ];
+
+    let verdicts: Vec<Verdict> = compare(&baseline, &rows, 10.0)
+        .into_iter()
+        .map(|comparison| comparison.verdict)
+        .collect();
+
+    assert_eq!(
+        verdicts,
+        vec![
+            Verdict::Unchanged,
+            Verdict::Regressed,
+            Verdict::Improved,
+            Verdict::New
+        ]
+    );
+}
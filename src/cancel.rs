@@ -0,0 +1,41 @@
+use crate::error::AocError;
+use crate::scoped::{self, Slot};
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static DEADLINE: Slot<Instant> = const { RefCell::new(None) };
+}
+
+/// Runs `function` with a deadline of `timeout` from now on this thread, or without one when `timeout`
+/// is `None`. Solutions notice the deadline by calling [check] in their long running loops.
+pub fn with_timeout<T>(timeout: Option<Duration>, function: impl FnOnce() -> T) -> T {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    scoped::with(&DEADLINE, deadline, function).0
+}
+
+/// Fails with [AocError::Cancelled] once the deadline of this thread has passed, so a search can give
+/// up with `cancel::check()?` instead of running on.
+pub fn check() -> Result<(), AocError> {
+    match scoped::get(&DEADLINE, |deadline| *deadline) {
+        Some(deadline) if Instant::now() >= deadline => Err(AocError::Cancelled),
+        _ => Ok(()),
+    }
+}
+
+#[test]
+fn test_check_deadline() {
+    let without = check();
+    let passed = with_timeout(Some(Duration::ZERO), check);
+    let pending = with_timeout(Some(Duration::from_secs(60)), check);
+    let nested = with_timeout(Some(Duration::from_secs(60)), || {
+        with_timeout(Some(Duration::ZERO), || {});
+        check()
+    });
+
+    assert!(without.is_ok());
+    assert!(matches!(passed, Err(AocError::Cancelled)));
+    assert!(pending.is_ok());
+    assert!(nested.is_ok());
+    assert!(check().is_ok());
+}
@@ -0,0 +1,1011 @@
+use crate::input::STDIN_PATH;
+use crate::log::Level;
+use crate::output::OutputFormat;
+use crate::solution::Part;
+use crate::stopwatch::BenchmarkOptions;
+use crate::visualize::Pace;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const USAGE: &str = "\
+Usage: aoc-2025 [--input-dir <PATH>] [--format <FORMAT>] [-q | -v | -vv] <command> [options]
+
+Commands:
+  run --day <N>       Run the solutions of a single day
+  run all             Run every solved day and print a summary
+  bench --day <N>     Benchmark a single day, or `bench all` for every day
+  verify [--day <N>]  Check the answers of all days, or one day, against answers.txt, which
+                      also takes --timeout
+  visualize --day <N> Draw the frames a day emits while it runs, for days that draw a grid
+  export --day <N>    Write the pictures a day draws while it runs as PPM and SVG images,
+                      or `export all` for every day
+  watch --day <N>     Run a day, and run it again whenever one of its inputs changes, or
+                      rebuild and restart when src/dayN.rs changes
+  new --day <N>       Generate src/dayN.rs and empty inputs for a new day, and register it,
+                      run from the root of the repository
+  fetch --day <N>     Download the puzzle input of a day, unless it was downloaded before
+  submit --day <N> --part <1|2>
+                      Send the answer to a part of the puzzle input, and keep it in the
+                      submissions.txt history next to the inputs
+  fuzz --day <N>      Check a day against a brute-force solution on random inputs, and print
+                      the smallest input they disagree on
+  help                Show this message
+
+Global options:
+      --input-dir <PATH>
+                      Read the inputs and answers.txt from this directory instead of
+                      $AOC_INPUT_DIR, or the puzzle-inputs directory when that is not set
+      --format <text|json|csv>
+                      Print the results of run and verify as a table (default), or as one
+                      record per day, part and input with the answer, timings and status
+  -q, --quiet         Only print the results and errors, without timings or answer messages
+  -v, --verbose       Also print what the solutions do step by step, repeat or use -vv for
+                      every detail
+
+Run options:
+  -d, --day <N>       The day to run
+  -p, --part <1|2>    Only run the given part
+  -i, --input <PATH>  Run against the given file instead of the puzzle inputs, may be repeated
+                      to run against several files, `-` reads standard input
+      --example-only  Only run against the example input, or read the --input files as examples
+                      for days where examples ask for something else, like day 8
+      --puzzle-only   Only run against the puzzle input
+      --timeout <MS>  Cancel a part that runs longer than this and report it as TIMEOUT,
+                      for solutions that check for cancellation
+
+Bench options, next to all run options:
+      --warmup <N>    Unmeasured runs before measuring starts (default 3)
+      --budget <MS>   Milliseconds to keep measuring each part for (default 1000)
+      --min-runs <N>  Measured runs to do even if the budget is spent (default 5)
+      --save <PATH>   Write the measured timings to a JSON baseline file
+      --compare <PATH>
+                      Compare the median timings against a saved baseline
+      --threshold <PERCENT>
+                      How much slower than the baseline counts as a regression (default 10)
+
+Visualize options, next to all run options:
+      --delay <MS>    Milliseconds between frames (default 100)
+      --step          Wait for Enter before every next frame, q and Enter stops
+
+Export options, next to all run options:
+  -o, --output-dir <PATH>
+                      Write the images to this directory (default the current directory)
+      --scale <N>     Pixels per grid cell (default 4)
+
+Watch options, next to all run options:
+      --interval <MS> Milliseconds between checks for changed files (default 500)
+      --also-watch <PATH>
+                      Also run again when this file changes, may be repeated
+
+Fetch and submit options:
+      --session-file <PATH>
+                      Read the session token from this file instead of $AOC_SESSION, or
+                      ~/.aoc-session when that is not set
+      --base-url <URL>
+                      Talk to this server instead of $AOC_BASE_URL, or
+                      https://adventofcode.com when that is not set
+
+Fuzz options:
+      --iterations <N>
+                      Random inputs to check (default 1000)
+      --seed <N>      Generate the inputs from this seed to repeat an earlier run, instead of
+                      a seed from the clock
+
+`aoc-2025 <N>` is a shorthand for `aoc-2025 run --day <N>`.";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunOptions),
+    Bench(BenchOptions),
+    Verify(VerifyOptions),
+    Watch(WatchOptions),
+    Visualize(VisualizeOptions),
+    Export(ExportOptions),
+    /// Scaffold a new day.
+    New(u8),
+    Fetch(FetchOptions),
+    Submit(SubmitOptions),
+    Fuzz(FuzzOptions),
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunOptions {
+    pub days: DaySelection,
+    pub part: Option<Part>,
+    pub inputs: InputSelection,
+    /// How long each part may run before it is cancelled.
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DaySelection {
+    Single(u8),
+    All,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    pub run: RunOptions,
+    pub benchmark: BenchmarkOptions,
+    pub save: Option<PathBuf>,
+    pub compare: Option<PathBuf>,
+    pub threshold_percent: f64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VerifyOptions {
+    pub days: DaySelection,
+    /// How long each part may run before it is cancelled and counted as timed out.
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct WatchOptions {
+    pub run: RunOptions,
+    pub interval: Duration,
+    pub also_watch: Vec<PathBuf>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VisualizeOptions {
+    pub run: RunOptions,
+    pub pace: Pace,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ExportOptions {
+    pub run: RunOptions,
+    pub output_dir: PathBuf,
+    pub scale: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FetchOptions {
+    pub day: u8,
+    pub session_file: Option<PathBuf>,
+    pub base_url: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SubmitOptions {
+    pub day: u8,
+    pub part: Part,
+    pub session_file: Option<PathBuf>,
+    pub base_url: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FuzzOptions {
+    pub day: u8,
+    pub iterations: u32,
+    /// `None` picks a seed from the clock.
+    pub seed: Option<u64>,
+}
+
+/// Which inputs a day is run against.
+#[derive(Debug, PartialEq)]
+pub enum InputSelection {
+    ExampleAndPuzzle,
+    ExampleOnly,
+    PuzzleOnly,
+    /// Files given with `--input`, where [STDIN_PATH] stands for standard input.
+    Files(Vec<PathBuf>),
+    /// Files given with `--input` along with `--example-only`, which are read as examples.
+    ExampleFiles(Vec<PathBuf>),
+}
+
+/// Options that apply to every command and may appear anywhere on the command line, except as the
+/// value of another option.
+#[derive(Debug, Default, PartialEq)]
+pub struct GlobalOptions {
+    pub input_dir: Option<PathBuf>,
+    pub verbosity: Level,
+    pub format: OutputFormat,
+}
+
+/// Command options that take a value, which is passed on as it is even when it looks like a global
+/// option, so `--input -q` reads a file named `-q`.
+const VALUE_OPTIONS: [&str; 23] = [
+    "-d",
+    "--day",
+    "-p",
+    "--part",
+    "-i",
+    "--input",
+    "--timeout",
+    "--warmup",
+    "--budget",
+    "--min-runs",
+    "--save",
+    "--compare",
+    "--threshold",
+    "--delay",
+    "-o",
+    "--output-dir",
+    "--scale",
+    "--interval",
+    "--also-watch",
+    "--session-file",
+    "--base-url",
+    "--iterations",
+    "--seed",
+];
+
+/// Takes the global options out of the command line and parses the command from what is left.
+pub fn parse_command_line(
+    args: impl IntoIterator<Item = String>,
+) -> Result<(GlobalOptions, Command), String> {
+    let mut global = GlobalOptions::default();
+    let mut command_args: Vec<String> = Vec::new();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input-dir" => {
+                global.input_dir = Some(PathBuf::from(expect_value(&arg, args.next())?));
+            }
+            "--format" => global.format = expect_value(&arg, args.next())?.parse()?,
+            "-q" | "--quiet" => global.verbosity = Level::Quiet,
+            "-v" | "--verbose" => global.verbosity = more_verbose(global.verbosity),
+            "-vv" => global.verbosity = more_verbose(more_verbose(global.verbosity)),
+            option if VALUE_OPTIONS.contains(&option) => {
+                command_args.push(arg);
+                command_args.extend(args.next());
+            }
+            _ => command_args.push(arg),
+        }
+    }
+
+    Ok((global, parse_args(command_args)?))
+}
+
+fn more_verbose(level: Level) -> Level {
+    match level {
+        Level::Quiet => Level::Info,
+        Level::Info => Level::Debug,
+        Level::Debug | Level::Trace => Level::Trace,
+    }
+}
+
+/// Parses the command line, without the program name.
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.into_iter();
+
+    let Some(command) = args.next() else {
+        return Err("No command given".to_owned());
+    };
+
+    match command.as_str() {
+        "run" => parse_run_options(args).map(Command::Run),
+        "bench" => parse_bench_options(args),
+        "verify" => parse_verify_options(args).map(Command::Verify),
+        "watch" => parse_watch_options(args).map(Command::Watch),
+        "visualize" => parse_visualize_options(args).map(Command::Visualize),
+        "export" => parse_export_options(args).map(Command::Export),
+        "new" => parse_new_options(args).map(Command::New),
+        "fetch" => parse_fetch_options(args).map(Command::Fetch),
+        "submit" => parse_submit_options(args).map(Command::Submit),
+        "fuzz" => parse_fuzz_options(args).map(Command::Fuzz),
+        "help" | "-h" | "--help" => Ok(Command::Help),
+        day if day.parse::<u8>().is_ok() => {
+            if let Some(other) = args.next() {
+                return Err(format!("Unknown option '{other}'"));
+            }
+
+            Ok(Command::Run(RunOptions {
+                days: DaySelection::Single(parse_day(day)?),
+                part: None,
+                inputs: InputSelection::ExampleAndPuzzle,
+                timeout: None,
+            }))
+        }
+        other => Err(format!("Unknown command '{other}'")),
+    }
+}
+
+fn parse_run_options(mut args: impl Iterator<Item = String>) -> Result<RunOptions, String> {
+    let mut day: Option<u8> = None;
+    let mut all = false;
+    let mut part: Option<Part> = None;
+    let mut example_only = false;
+    let mut puzzle_only = false;
+    let mut files: Vec<PathBuf> = Vec::new();
+    let mut timeout: Option<Duration> = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "all" => all = true,
+            "-d" | "--day" => day = Some(parse_day(&expect_value(&arg, args.next())?)?),
+            "-p" | "--part" => part = Some(parse_part(&expect_value(&arg, args.next())?)?),
+            "-i" | "--input" => files.push(PathBuf::from(expect_value(&arg, args.next())?)),
+            "--example-only" => example_only = true,
+            "--puzzle-only" => puzzle_only = true,
+            "--timeout" => {
+                timeout = Some(Duration::from_millis(
+                    parse_number(&arg, args.next())?.into(),
+                ))
+            }
+            other => return Err(format!("Unknown option '{other}'")),
+        }
+    }
+
+    let days = match (day, all) {
+        (Some(day), false) => DaySelection::Single(day),
+        (None, true) => DaySelection::All,
+        (Some(_), true) => return Err("--day and all cannot be combined".to_owned()),
+        (None, false) => return Err("Missing required option --day".to_owned()),
+    };
+
+    if days == DaySelection::All && !files.is_empty() {
+        return Err("--input cannot be used when running all days".to_owned());
+    }
+    if files
+        .iter()
+        .filter(|path| *path == Path::new(STDIN_PATH))
+        .count()
+        > 1
+    {
+        return Err(format!("--input {STDIN_PATH} can only be given once"));
+    }
+
+    let inputs = match (example_only, puzzle_only, files.is_empty()) {
+        (false, false, true) => InputSelection::ExampleAndPuzzle,
+        (true, false, true) => InputSelection::ExampleOnly,
+        (false, true, true) => InputSelection::PuzzleOnly,
+        (false, false, false) => InputSelection::Files(files),
+        (true, false, false) => InputSelection::ExampleFiles(files),
+        _ => {
+            return Err(
+                "--puzzle-only cannot be combined with --input or --example-only".to_owned(),
+            );
+        }
+    };
+
+    Ok(RunOptions {
+        days,
+        part,
+        inputs,
+        timeout,
+    })
+}
+
+fn parse_bench_options(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut benchmark_options = BenchmarkOptions::default();
+    let mut save: Option<PathBuf> = None;
+    let mut compare: Option<PathBuf> = None;
+    let mut threshold_percent = 10.0;
+    let mut run_args: Vec<String> = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--warmup" => {
+                benchmark_options.warm_up_iterations = parse_number(&arg, args.next())?;
+            }
+            "--budget" => {
+                benchmark_options.budget =
+                    Duration::from_millis(parse_number(&arg, args.next())?.into());
+            }
+            "--min-runs" => {
+                benchmark_options.min_iterations = parse_number(&arg, args.next())?;
+            }
+            "--save" => save = Some(PathBuf::from(expect_value(&arg, args.next())?)),
+            "--compare" => compare = Some(PathBuf::from(expect_value(&arg, args.next())?)),
+            "--threshold" => threshold_percent = parse_number(&arg, args.next())?.into(),
+            _ => run_args.push(arg),
+        }
+    }
+
+    Ok(Command::Bench(BenchOptions {
+        run: parse_run_options(run_args.into_iter())?,
+        benchmark: benchmark_options,
+        save,
+        compare,
+        threshold_percent,
+    }))
+}
+
+fn parse_watch_options(mut args: impl Iterator<Item = String>) -> Result<WatchOptions, String> {
+    let mut interval = Duration::from_millis(500);
+    let mut also_watch: Vec<PathBuf> = Vec::new();
+    let mut run_args: Vec<String> = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--interval" => {
+                interval = Duration::from_millis(parse_number(&arg, args.next())?.into())
+            }
+            "--also-watch" => also_watch.push(PathBuf::from(expect_value(&arg, args.next())?)),
+            _ => run_args.push(arg),
+        }
+    }
+
+    let run = parse_run_options(run_args.into_iter())?;
+    if run.days == DaySelection::All {
+        return Err("watch needs a single day, use --day".to_owned());
+    }
+    if let InputSelection::Files(files) | InputSelection::ExampleFiles(files) = &run.inputs
+        && files.iter().any(|path| path == Path::new(STDIN_PATH))
+    {
+        return Err(format!(
+            "watch cannot read --input {STDIN_PATH}, it has no file to watch"
+        ));
+    }
+
+    Ok(WatchOptions {
+        run,
+        interval,
+        also_watch,
+    })
+}
+
+fn parse_visualize_options(
+    mut args: impl Iterator<Item = String>,
+) -> Result<VisualizeOptions, String> {
+    let mut delay = Duration::from_millis(100);
+    let mut step = false;
+    let mut run_args: Vec<String> = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--delay" => delay = Duration::from_millis(parse_number(&arg, args.next())?.into()),
+            "--step" => step = true,
+            _ => run_args.push(arg),
+        }
+    }
+
+    let run = parse_run_options(run_args.into_iter())?;
+    if run.days == DaySelection::All {
+        return Err("visualize needs a single day, use --day".to_owned());
+    }
+
+    Ok(VisualizeOptions {
+        run,
+        pace: if step { Pace::Step } else { Pace::Delay(delay) },
+    })
+}
+
+fn parse_export_options(mut args: impl Iterator<Item = String>) -> Result<ExportOptions, String> {
+    let mut output_dir = PathBuf::from(".");
+    let mut scale = 4;
+    let mut run_args: Vec<String> = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output-dir" => output_dir = PathBuf::from(expect_value(&arg, args.next())?),
+            "--scale" => match parse_number(&arg, args.next())? {
+                0 => return Err("--scale must be at least 1".to_owned()),
+                value => scale = value as usize,
+            },
+            _ => run_args.push(arg),
+        }
+    }
+
+    Ok(ExportOptions {
+        run: parse_run_options(run_args.into_iter())?,
+        output_dir,
+        scale,
+    })
+}
+
+fn parse_number(option: &str, value: Option<String>) -> Result<u32, String> {
+    let value = expect_value(option, value)?;
+
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value '{value}' for {option}, expected a number"))
+}
+
+fn parse_verify_options(mut args: impl Iterator<Item = String>) -> Result<VerifyOptions, String> {
+    let mut days = DaySelection::All;
+    let mut timeout: Option<Duration> = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-d" | "--day" => {
+                days = DaySelection::Single(parse_day(&expect_value(&arg, args.next())?)?)
+            }
+            "--timeout" => {
+                timeout = Some(Duration::from_millis(
+                    parse_number(&arg, args.next())?.into(),
+                ))
+            }
+            other => return Err(format!("Unknown option '{other}'")),
+        }
+    }
+
+    Ok(VerifyOptions { days, timeout })
+}
+
+fn parse_new_options(mut args: impl Iterator<Item = String>) -> Result<u8, String> {
+    let mut day: Option<u8> = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-d" | "--day" => day = Some(parse_day(&expect_value(&arg, args.next())?)?),
+            other => return Err(format!("Unknown option '{other}'")),
+        }
+    }
+
+    day.ok_or_else(|| "Missing required option --day".to_owned())
+}
+
+fn parse_fetch_options(mut args: impl Iterator<Item = String>) -> Result<FetchOptions, String> {
+    let mut day: Option<u8> = None;
+    let mut session_file: Option<PathBuf> = None;
+    let mut base_url: Option<String> = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-d" | "--day" => day = Some(parse_day(&expect_value(&arg, args.next())?)?),
+            "--session-file" => {
+                session_file = Some(PathBuf::from(expect_value(&arg, args.next())?))
+            }
+            "--base-url" => base_url = Some(expect_value(&arg, args.next())?),
+            other => return Err(format!("Unknown option '{other}'")),
+        }
+    }
+
+    Ok(FetchOptions {
+        day: day.ok_or_else(|| "Missing required option --day".to_owned())?,
+        session_file,
+        base_url,
+    })
+}
+
+fn parse_submit_options(mut args: impl Iterator<Item = String>) -> Result<SubmitOptions, String> {
+    let mut day: Option<u8> = None;
+    let mut part: Option<Part> = None;
+    let mut session_file: Option<PathBuf> = None;
+    let mut base_url: Option<String> = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-d" | "--day" => day = Some(parse_day(&expect_value(&arg, args.next())?)?),
+            "-p" | "--part" => part = Some(parse_part(&expect_value(&arg, args.next())?)?),
+            "--session-file" => {
+                session_file = Some(PathBuf::from(expect_value(&arg, args.next())?))
+            }
+            "--base-url" => base_url = Some(expect_value(&arg, args.next())?),
+            other => return Err(format!("Unknown option '{other}'")),
+        }
+    }
+
+    Ok(SubmitOptions {
+        day: day.ok_or_else(|| "Missing required option --day".to_owned())?,
+        part: part.ok_or_else(|| "Missing required option --part".to_owned())?,
+        session_file,
+        base_url,
+    })
+}
+
+fn parse_fuzz_options(mut args: impl Iterator<Item = String>) -> Result<FuzzOptions, String> {
+    let mut day: Option<u8> = None;
+    let mut iterations = 1000;
+    let mut seed: Option<u64> = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-d" | "--day" => day = Some(parse_day(&expect_value(&arg, args.next())?)?),
+            "--iterations" => iterations = parse_number(&arg, args.next())?,
+            "--seed" => {
+                let value = expect_value(&arg, args.next())?;
+                seed = Some(value.parse().map_err(|_| {
+                    format!("Invalid value '{value}' for {arg}, expected a number")
+                })?);
+            }
+            other => return Err(format!("Unknown option '{other}'")),
+        }
+    }
+
+    Ok(FuzzOptions {
+        day: day.ok_or_else(|| "Missing required option --day".to_owned())?,
+        iterations,
+        seed,
+    })
+}
+
+fn expect_value(option: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("Option {option} requires a value"))
+}
+
+fn parse_day(text: &str) -> Result<u8, String> {
+    match text.parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!(
+            "Invalid day '{text}', expected a number from 1 to 25"
+        )),
+    }
+}
+
+fn parse_part(text: &str) -> Result<Part, String> {
+    match text {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        other => Err(format!("Invalid part '{other}', expected 1 or 2")),
+    }
+}
+
+#[cfg(test)]
+fn args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(str::to_owned).collect()
+}
+
+#[test]
+fn test_parse_run_command() {
+    assert_eq!(
+        parse_args(args("run --day 7 --part 2 --input some/file.txt")),
+        Ok(Command::Run(RunOptions {
+            days: DaySelection::Single(7),
+            part: Some(Part::Two),
+            inputs: InputSelection::Files(vec![PathBuf::from("some/file.txt")]),
+            timeout: None,
+        }))
+    );
+    assert_eq!(
+        parse_args(args("run -d 3 --example-only")),
+        Ok(Command::Run(RunOptions {
+            days: DaySelection::Single(3),
+            part: None,
+            inputs: InputSelection::ExampleOnly,
+            timeout: None,
+        }))
+    );
+}
+
+#[test]
+fn test_parse_timeout() {
+    let Ok(Command::Run(options)) = parse_args(args("run --day 10 --part 2 --timeout 5000")) else {
+        panic!("Expected a run command");
+    };
+    let Ok(Command::Bench(bench)) = parse_args(args("bench --day 10 --timeout 100")) else {
+        panic!("Expected a bench command");
+    };
+
+    assert_eq!(options.timeout, Some(Duration::from_secs(5)));
+    assert_eq!(bench.run.timeout, Some(Duration::from_millis(100)));
+}
+
+#[test]
+fn test_parse_several_inputs() {
+    let Ok(Command::Run(options)) = parse_args(args("run --day 1 -i a.txt --input - -i b.txt"))
+    else {
+        panic!("Expected a run command");
+    };
+
+    assert_eq!(
+        options.inputs,
+        InputSelection::Files(vec![
+            PathBuf::from("a.txt"),
+            PathBuf::from("-"),
+            PathBuf::from("b.txt")
+        ])
+    );
+    assert!(parse_args(args("run --day 1 --input - --input -")).is_err());
+    assert!(parse_args(args("run --day 1 --input a.txt --puzzle-only")).is_err());
+    assert_eq!(
+        parse_args(args("run --day 8 --input a.txt --example-only")).map(|command| match command {
+            Command::Run(options) => options.inputs,
+            _ => panic!("Expected a run command"),
+        }),
+        Ok(InputSelection::ExampleFiles(vec![PathBuf::from("a.txt")]))
+    );
+    assert!(parse_args(args("watch --day 1 --input -")).is_err());
+}
+
+#[test]
+fn test_parse_day_shorthand() {
+    assert_eq!(
+        parse_args(args("11")),
+        Ok(Command::Run(RunOptions {
+            days: DaySelection::Single(11),
+            part: None,
+            inputs: InputSelection::ExampleAndPuzzle,
+            timeout: None,
+        }))
+    );
+    assert!(parse_args(args("7 extra")).is_err());
+}
+
+#[test]
+fn test_parse_run_all() {
+    assert_eq!(
+        parse_args(args("run all --puzzle-only")),
+        Ok(Command::Run(RunOptions {
+            days: DaySelection::All,
+            part: None,
+            inputs: InputSelection::PuzzleOnly,
+            timeout: None,
+        }))
+    );
+}
+
+#[test]
+fn test_parse_bench() {
+    let Ok(Command::Bench(options)) =
+        parse_args(args("bench --day 10 --warmup 0 --part 2 --budget 250"))
+    else {
+        panic!("Expected a bench command");
+    };
+
+    assert_eq!(options.run.days, DaySelection::Single(10));
+    assert_eq!(options.run.part, Some(Part::Two));
+    assert_eq!(options.benchmark.warm_up_iterations, 0);
+    assert_eq!(options.benchmark.budget, Duration::from_millis(250));
+    assert_eq!(options.benchmark.min_iterations, 5);
+    assert_eq!(options.compare, None);
+    assert_eq!(options.threshold_percent, 10.0);
+}
+
+#[test]
+fn test_parse_bench_baseline() {
+    let Ok(Command::Bench(options)) = parse_args(args(
+        "bench all --save new.json --compare old.json --threshold 25",
+    )) else {
+        panic!("Expected a bench command");
+    };
+
+    assert_eq!(options.save, Some(PathBuf::from("new.json")));
+    assert_eq!(options.compare, Some(PathBuf::from("old.json")));
+    assert_eq!(options.threshold_percent, 25.0);
+}
+
+#[test]
+fn test_parse_verify() {
+    assert_eq!(
+        parse_args(args("verify")),
+        Ok(Command::Verify(VerifyOptions {
+            days: DaySelection::All,
+            timeout: None
+        }))
+    );
+    assert_eq!(
+        parse_args(args("verify --day 5 --timeout 250")),
+        Ok(Command::Verify(VerifyOptions {
+            days: DaySelection::Single(5),
+            timeout: Some(Duration::from_millis(250))
+        }))
+    );
+}
+
+#[test]
+fn test_parse_watch() {
+    assert_eq!(
+        parse_args(args(
+            "watch --day 7 --part 1 --example-only --interval 100 --also-watch a.txt --also-watch b.txt"
+        )),
+        Ok(Command::Watch(WatchOptions {
+            run: RunOptions {
+                days: DaySelection::Single(7),
+                part: Some(Part::One),
+                inputs: InputSelection::ExampleOnly,
+                timeout: None,
+            },
+            interval: Duration::from_millis(100),
+            also_watch: vec![PathBuf::from("a.txt"), PathBuf::from("b.txt")],
+        }))
+    );
+    assert!(parse_args(args("watch all")).is_err());
+    assert!(parse_args(args("watch --day 7 --interval")).is_err());
+}
+
+#[test]
+fn test_parse_visualize() {
+    let pace = |line: &str| match parse_args(args(line)) {
+        Ok(Command::Visualize(options)) => Ok(options.pace),
+        Ok(other) => panic!("Expected a visualize command, got {other:?}"),
+        Err(message) => Err(message),
+    };
+
+    assert_eq!(
+        pace("visualize --day 4"),
+        Ok(Pace::Delay(Duration::from_millis(100)))
+    );
+    assert_eq!(
+        pace("visualize --day 7 --part 1 --delay 20"),
+        Ok(Pace::Delay(Duration::from_millis(20)))
+    );
+    assert_eq!(
+        pace("visualize --step --day 4 --example-only"),
+        Ok(Pace::Step)
+    );
+    assert!(pace("visualize all").is_err());
+}
+
+#[test]
+fn test_parse_export() {
+    let Ok(Command::Export(options)) = parse_args(args("export --day 9 -o pictures --scale 8"))
+    else {
+        panic!("Expected an export command");
+    };
+
+    assert_eq!(options.run.days, DaySelection::Single(9));
+    assert_eq!(options.output_dir, PathBuf::from("pictures"));
+    assert_eq!(options.scale, 8);
+    assert!(matches!(
+        parse_args(args("export all")),
+        Ok(Command::Export(ExportOptions { scale: 4, .. }))
+    ));
+    assert!(parse_args(args("export --day 9 --scale 0")).is_err());
+}
+
+#[test]
+fn test_parse_new() {
+    assert_eq!(parse_args(args("new --day 12")), Ok(Command::New(12)));
+    assert_eq!(parse_args(args("new -d 3")), Ok(Command::New(3)));
+    assert!(parse_args(args("new")).is_err());
+    assert!(parse_args(args("new --day 26")).is_err());
+}
+
+#[test]
+fn test_parse_fetch() {
+    assert_eq!(
+        parse_args(args("fetch --day 4")),
+        Ok(Command::Fetch(FetchOptions {
+            day: 4,
+            session_file: None,
+            base_url: None,
+        }))
+    );
+    assert_eq!(
+        parse_args(args(
+            "fetch --base-url http://127.0.0.1:8080 -d 4 --session-file token.txt"
+        )),
+        Ok(Command::Fetch(FetchOptions {
+            day: 4,
+            session_file: Some(PathBuf::from("token.txt")),
+            base_url: Some("http://127.0.0.1:8080".to_owned()),
+        }))
+    );
+    assert!(parse_args(args("fetch")).is_err());
+    assert!(parse_args(args("fetch --day 4 --base-url")).is_err());
+}
+
+#[test]
+fn test_parse_submit() {
+    assert_eq!(
+        parse_args(args("submit --day 9 -p 2 --base-url http://127.0.0.1:8080")),
+        Ok(Command::Submit(SubmitOptions {
+            day: 9,
+            part: Part::Two,
+            session_file: None,
+            base_url: Some("http://127.0.0.1:8080".to_owned()),
+        }))
+    );
+    assert!(parse_args(args("submit --day 9")).is_err());
+    assert!(parse_args(args("submit --part 1")).is_err());
+    assert!(parse_args(args("submit --day 9 --part 3")).is_err());
+}
+
+#[test]
+fn test_parse_fuzz() {
+    assert_eq!(
+        parse_args(args(
+            "fuzz --day 5 --iterations 200 --seed 18446744073709551615"
+        )),
+        Ok(Command::Fuzz(FuzzOptions {
+            day: 5,
+            iterations: 200,
+            seed: Some(u64::MAX),
+        }))
+    );
+    assert_eq!(
+        parse_args(args("fuzz -d 3")),
+        Ok(Command::Fuzz(FuzzOptions {
+            day: 3,
+            iterations: 1000,
+            seed: None,
+        }))
+    );
+    assert!(parse_args(args("fuzz --iterations 10")).is_err());
+    assert!(parse_args(args("fuzz --day 3 --seed -1")).is_err());
+}
+
+#[test]
+fn test_parse_global_options() {
+    let (global, command) =
+        parse_command_line(args("run --input-dir /tmp/inputs --day 2")).unwrap();
+
+    assert_eq!(global.input_dir, Some(PathBuf::from("/tmp/inputs")));
+    assert_eq!(
+        command,
+        Command::Run(RunOptions {
+            days: DaySelection::Single(2),
+            part: None,
+            inputs: InputSelection::ExampleAndPuzzle,
+            timeout: None,
+        })
+    );
+    assert_eq!(
+        parse_command_line(args("verify")),
+        Ok((
+            GlobalOptions::default(),
+            Command::Verify(VerifyOptions {
+                days: DaySelection::All,
+                timeout: None
+            })
+        ))
+    );
+    assert!(parse_command_line(args("verify --input-dir")).is_err());
+}
+
+#[test]
+fn test_parse_format() {
+    let format = |line: &str| parse_command_line(args(line)).map(|(global, _)| global.format);
+
+    assert_eq!(format("run all"), Ok(OutputFormat::Text));
+    assert_eq!(format("run all --format json"), Ok(OutputFormat::Json));
+    assert_eq!(format("--format csv verify --day 3"), Ok(OutputFormat::Csv));
+    assert!(format("run all --format yaml").is_err());
+    assert!(format("run all --format").is_err());
+}
+
+#[test]
+fn test_parse_verbosity() {
+    let verbosity = |line: &str| parse_command_line(args(line)).unwrap().0.verbosity;
+
+    assert_eq!(verbosity("verify"), Level::Info);
+    assert_eq!(verbosity("-q verify"), Level::Quiet);
+    assert_eq!(verbosity("run --day 7 --quiet"), Level::Quiet);
+    assert_eq!(verbosity("run -v --day 7"), Level::Debug);
+    assert_eq!(verbosity("run --day 7 -v -v"), Level::Trace);
+    assert_eq!(verbosity("run --day 7 -vv"), Level::Trace);
+    assert_eq!(verbosity("run --day 7 -vv -v"), Level::Trace);
+    assert_eq!(verbosity("run --day 7 --input -q"), Level::Info);
+}
+
+#[test]
+fn test_global_options_are_not_taken_from_values() {
+    let (global, command) = parse_command_line(args("run --day 7 -i -q -i --format")).unwrap();
+
+    assert_eq!(global, GlobalOptions::default());
+    assert_eq!(
+        command,
+        Command::Run(RunOptions {
+            days: DaySelection::Single(7),
+            part: None,
+            inputs: InputSelection::Files(vec![PathBuf::from("-q"), PathBuf::from("--format")]),
+            timeout: None,
+        })
+    );
+}
+
+#[test]
+fn test_every_option_with_a_value_is_known() {
+    for line in USAGE.lines() {
+        let Some((options, _)) = line.split_once(" <") else {
+            continue;
+        };
+        let Some(option) = options
+            .split([' ', ','])
+            .rfind(|word| word.starts_with('-'))
+        else {
+            continue;
+        };
+
+        if !["--input-dir", "--format"].contains(&option) {
+            assert!(
+                VALUE_OPTIONS.contains(&option),
+                "{option} is not in VALUE_OPTIONS"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_parse_invalid_arguments() {
+    assert!(parse_args(args("")).is_err());
+    assert!(parse_args(args("launch")).is_err());
+    assert!(parse_args(args("run")).is_err());
+    assert!(parse_args(args("run --day")).is_err());
+    assert!(parse_args(args("run --day 0")).is_err());
+    assert!(parse_args(args("run --day 7 --part 3")).is_err());
+    assert!(parse_args(args("run --day 7 --example-only --puzzle-only")).is_err());
+    assert!(parse_args(args("run --day 3 all")).is_err());
+    assert!(parse_args(args("bench all -d 3")).is_err());
+    assert!(parse_args(args("run --day 7 --verbose")).is_err());
+    assert!(parse_args(args("run all --input some/file.txt")).is_err());
+    assert!(parse_args(args("run --day 10 --timeout")).is_err());
+    assert!(parse_args(args("verify --part 1")).is_err());
+    assert!(parse_args(args("bench all --budget soon")).is_err());
+}
@@ -1,39 +1,72 @@
 use std::fmt::{Display, Formatter};
-use std::fs::read_to_string;
 
 const DIAL_MAX: u8 = 100;
 
-use crate::stopwatch::time;
+use crate::error::{AocError, ParseError};
+use crate::log::{info, trace};
+use crate::puzzle_input::PuzzleInput;
+use crate::solution::Solution;
 use Turn::Left;
 use Turn::Right;
 use std::str::FromStr;
 
-pub fn run() {
-    let example_data = read_to_string("./puzzle-inputs/day-1-example.txt").unwrap_or_else(|_| {
-        panic!(
-            "Failed to read file {}",
-            "./puzzle-inputs/day-1-example.txt"
-        )
-    });
-    let puzzle_data = read_to_string("./puzzle-inputs/day-1-input.txt")
-        .unwrap_or_else(|_| panic!("Failed to read file {}", "./puzzle-inputs/day-1-input.txt"));
-
-    time("Day 1, Part 1 Example", || {
-        run_part_1(example_data.as_str())
-    }); // 3
-    time("Day 1, Part 1 Puzzle", || run_part_1(puzzle_data.as_str()));
-
-    time("Day 1, Part 2 Example", || {
-        run_part_2(example_data.as_str())
-    }); // 6
-    time("Day 1, Part 2 Puzzle", || run_part_2(puzzle_data.as_str()));
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<Turn>;
+    type Answer1 = u16;
+    type Answer2 = u16;
+
+    fn parse(&self, input: &PuzzleInput) -> Result<Vec<Turn>, AocError> {
+        input.parse_lines(parse_turn)
+    }
+
+    fn part_1(&self, turns: &Vec<Turn>) -> Result<u16, AocError> {
+        let mut dial = Dial { position: 50 };
+        let mut amount_of_0_positions = 0;
+
+        turns.iter().for_each(|turn| {
+            dial.turn(*turn);
+
+            if dial.position == 0 {
+                amount_of_0_positions += 1;
+            }
+        });
+
+        info!(
+            "The dial position is {}, the amount of 0 positions found was {}.",
+            dial.position, amount_of_0_positions
+        );
+
+        Ok(amount_of_0_positions)
+    }
+
+    fn part_2(&self, turns: &Vec<Turn>) -> Result<u16, AocError> {
+        let mut dial = Dial { position: 50 };
+
+        let mut amount_of_0_positions = 0;
+
+        turns.iter().for_each(|turn| {
+            amount_of_0_positions += dial.turn(*turn);
+        });
+
+        info!(
+            "The dial position is {}, the amount of times the dial hit 0 was {}.",
+            dial.position, amount_of_0_positions
+        );
+
+        Ok(amount_of_0_positions)
+    }
 }
 
 struct Dial {
     position: u8,
 }
 
-enum Turn {
+#[derive(Copy, Clone)]
+pub enum Turn {
     Left(u16),
     Right(u16),
 }
@@ -51,20 +84,21 @@ impl Dial {
     /// Turns the dial according to direction and amount of turn.
     /// Returns the amount of times that 0 was passed.
     fn turn(&mut self, turn: Turn) -> u16 {
-        let mut next_value: i16 = self.position.into();
+        // Counts go up to u16::MAX, so the arithmetic is done in i32 where they cannot overflow.
+        let mut next_value: i32 = self.position.into();
 
         next_value += match turn {
-            Right(n) => n as i16,
-            Left(n) => -(n as i16),
+            Right(n) => i32::from(n),
+            Left(n) => -i32::from(n),
         };
 
-        let from = self.position as i16;
+        let from = self.position as i32;
         let to = next_value;
 
         // Handle the overflow
-        next_value %= DIAL_MAX as i16;
+        next_value %= DIAL_MAX as i32;
         if next_value < 0 {
-            next_value = DIAL_MAX as i16 - next_value.abs();
+            next_value = DIAL_MAX as i32 - next_value.abs();
         }
 
         self.position = next_value.try_into().unwrap_or_else(|_| {
@@ -74,88 +108,62 @@ impl Dial {
             )
         });
 
-        print!(
-            "The dial is rotated {} to point at {} ({})",
-            turn, self.position, to
-        );
-
         let mut amount_of_0s_hit = 0;
         if to <= 0 {
-            amount_of_0s_hit = to / -(DIAL_MAX as i16) + 1;
-            println!(
-                "; during this rotation, it points to 0 {} times.",
-                amount_of_0s_hit
-            );
-
-            if from == 0 {
-                amount_of_0s_hit -= 1;
-            }
+            amount_of_0s_hit = to / -(DIAL_MAX as i32) + 1;
         } else if to > 99 {
-            amount_of_0s_hit = to / (DIAL_MAX as i16);
-            println!(
-                "; during this rotation, it points to 0 {} times.",
-                amount_of_0s_hit
+            amount_of_0s_hit = to / (DIAL_MAX as i32);
+        }
+
+        if amount_of_0s_hit > 0 {
+            trace!(
+                "The dial is rotated {} to point at {} ({}); during this rotation, it points to 0 {} times.",
+                turn, self.position, to, amount_of_0s_hit
             );
         } else {
-            println!(".");
+            trace!(
+                "The dial is rotated {} to point at {} ({}).",
+                turn, self.position, to
+            );
+        }
+
+        if to <= 0 && from == 0 {
+            amount_of_0s_hit -= 1;
         }
 
         amount_of_0s_hit as u16
     }
 }
 
-fn run_part_1(input: &str) {
-    let mut dial = Dial { position: 50 };
-    let mut amount_of_0_positions = 0;
-
-    input.lines().for_each(|line| {
-        let direction = parse_turn(&line);
-
-        dial.turn(direction);
-
-        if dial.position == 0 {
-            amount_of_0_positions += 1;
+pub fn parse_turn(line: &str) -> Result<Turn, ParseError> {
+    let mut chars = line.chars();
+    let Some(direction) = chars.next() else {
+        return Err(ParseError::new(
+            0,
+            "expected a turn like 'L68', found an empty line",
+        ));
+    };
+
+    let turn: fn(u16) -> Turn = match direction {
+        'R' => Right,
+        'L' => Left,
+        other => {
+            return Err(ParseError::new(
+                0,
+                format!("unexpected direction '{other}', expected 'L' or 'R'"),
+            ));
         }
-    });
-
-    println!(
-        "The dial position is {}, the amount of 0 positions found was {}.",
-        dial.position, amount_of_0_positions
-    );
-}
-
-fn run_part_2(input: &str) {
-    let mut dial = Dial { position: 50 };
-
-    let mut amount_of_0_positions = 0;
-
-    input.lines().for_each(|line| {
-        let direction = parse_turn(&line);
-
-        amount_of_0_positions += dial.turn(direction);
-    });
-
-    println!(
-        "The dial position is {}, the amount of times the dial hit 0 was {}.",
-        dial.position, amount_of_0_positions
-    );
-}
-
-fn parse_turn(line: &&str) -> Turn {
-    let chars = &line[..1];
-    let count = &line[1..line.len()];
-    let count: u16 =
-        u16::from_str(count).unwrap_or_else(|_| panic!("Failed parsing count {}", count));
+    };
 
-    let direction = chars.chars().next();
+    let count_text = chars.as_str();
+    let count = u16::from_str(count_text).map_err(|_| {
+        ParseError::new(
+            direction.len_utf8(),
+            format!("invalid count '{count_text}'"),
+        )
+    })?;
 
-    match direction {
-        Some('R') => Right(count),
-        Some('L') => Left(count),
-        _ => {
-            panic!("Unexpected direction")
-        }
-    }
+    Ok(turn(count))
 }
 
 #[test]
@@ -224,3 +232,26 @@ fn dial_should_work() {
         "Turning left 101 times from position 0 should count as 0 being hit once."
     );
 }
+
+#[test]
+fn test_parse_turn_errors() {
+    assert!(matches!(parse_turn("L68"), Ok(Left(68))));
+    assert_eq!(parse_turn("").err().map(|error| error.offset), Some(0));
+    assert_eq!(parse_turn("R").err().map(|error| error.offset), Some(1));
+    assert_eq!(parse_turn("X5").err().map(|error| error.offset), Some(0));
+    assert_eq!(parse_turn("éx").err().map(|error| error.offset), Some(0));
+    assert_eq!(parse_turn("L1x").err().map(|error| error.offset), Some(1));
+}
+
+#[test]
+fn test_turn_large_counts() {
+    let mut dial = Dial { position: 50 };
+    assert_eq!(dial.turn(Right(32767)), 328);
+    assert_eq!(dial.position, 17);
+
+    let mut dial = Dial { position: 50 };
+    assert_eq!(dial.turn(Left(u16::MAX)), 655);
+    assert_eq!(dial.position, 15);
+
+    assert!(matches!(parse_turn("R65535"), Ok(Right(65535))));
+}
@@ -1,92 +1,83 @@
-use crate::stopwatch::time;
+use crate::cancel;
+use crate::error::{AocError, ParseError};
+use crate::log::{debug, info};
+use crate::puzzle_input::PuzzleInput;
+use crate::solution::Solution;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter, Write};
-use std::fs::read_to_string;
 use std::ops::{Add, BitAnd, BitXor};
 use std::str::FromStr;
 
-pub fn run() {
-    let example_data = read_to_string("./puzzle-inputs/day-10-example.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-10-example.txt", err
-        )
-    });
-    let puzzle_data = read_to_string("./puzzle-inputs/day-10-input.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-10-input.txt", err
-        )
-    });
-
-    time("Day 10, Part 1 Example", || {
-        run_part_1(example_data.as_str());
-    });
-    time("Day 10, Part 1 Puzzle", || {
-        run_part_1(puzzle_data.as_str());
-    });
+pub struct Day10;
 
-    // TODO: Solve part 2 efficiently. According to hints, this problem is related to constraint solving.
-    time("Day 10, Part 2 Example", || {
-        run_part_2(example_data.as_str());
-    });
-    time("Day 10, Part 2 Puzzle", || {
-        run_part_2(puzzle_data.as_str());
-    });
-}
+impl Solution for Day10 {
+    const DAY: u8 = 10;
 
-fn run_part_1(input: &str) -> u16 {
-    let input = input.replace('\r', "");
+    type Input = Vec<Machine>;
+    type Answer1 = u16;
+    type Answer2 = u16;
 
-    let machines: Vec<Machine> = parse_machines(input.as_str());
-
-    let minimal_button_presses = machines
-        .iter()
-        .map(|machine| {
-            let buttons = machine.find_least_amount_of_buttons_to_enable_machine();
+    fn parse(&self, input: &PuzzleInput) -> Result<Vec<Machine>, AocError> {
+        input.parse_lines(parse_machine)
+    }
 
-            buttons.len() as u16
-        })
-        .sum();
+    fn part_1(&self, machines: &Vec<Machine>) -> Result<u16, AocError> {
+        let minimal_button_presses = machines
+            .iter()
+            .map(|machine| {
+                let buttons = machine
+                    .find_least_amount_of_buttons_to_enable_machine()
+                    .ok_or_else(|| unsolvable_machine(machine, "lights"))?;
 
-    println!(
-        "It takes a minimum of {minimal_button_presses} presses to enable all lights on all machines."
-    );
+                Ok(buttons.len() as u16)
+            })
+            .sum::<Result<u16, AocError>>()?;
 
-    minimal_button_presses
-}
+        info!(
+            "It takes a minimum of {minimal_button_presses} presses to enable all lights on all machines."
+        );
 
-fn run_part_2(input: &str) -> u16 {
-    let input = input.replace('\r', "");
+        Ok(minimal_button_presses)
+    }
 
-    let machines: Vec<Machine> = parse_machines(input.as_str());
+    // TODO: Solve part 2 efficiently. According to hints, this problem is related to constraint solving.
+    fn part_2(&self, machines: &Vec<Machine>) -> Result<u16, AocError> {
+        let minimal_button_presses: u16 = machines
+            .iter()
+            .map(|machine| {
+                let buttons = machine
+                    .find_least_amount_of_buttons_to_configure_machine()?
+                    .ok_or_else(|| unsolvable_machine(machine, "joltages"))?;
 
-    let minimal_button_presses: u16 = machines
-        .iter()
-        .map(|machine| {
-            let buttons = machine.find_least_amount_of_buttons_to_configure_machine();
+                debug!("Took {} button presses for machine", buttons.len());
 
-            println!("Took {} button presses for machine", buttons.len());
+                Ok(buttons.len() as u16)
+            })
+            .sum::<Result<u16, AocError>>()?;
 
-            buttons.len() as u16
-        })
-        .sum();
+        info!(
+            "It takes a minimum of {minimal_button_presses} presses to enable all lights on all machines."
+        );
 
-    println!(
-        "It takes a minimum of {minimal_button_presses} presses to enable all lights on all machines."
-    );
+        Ok(minimal_button_presses)
+    }
+}
 
-    minimal_button_presses
+fn unsolvable_machine(machine: &Machine, target: &str) -> AocError {
+    AocError::Unsolvable(format!(
+        "no button presses reach the wanted {target} of machine {}",
+        machine.wanted_indicators
+    ))
 }
 
-struct Machine {
-    wanted_indicators: IndicatorLights,
-    buttons: Vec<Button>,
-    joltages: Vec<u16>,
+pub struct Machine {
+    pub wanted_indicators: IndicatorLights,
+    pub buttons: Vec<Button>,
+    pub joltages: Vec<u16>,
 }
 
 impl Machine {
-    fn find_least_amount_of_buttons_to_enable_machine(&self) -> Vec<Button> {
+    pub fn find_least_amount_of_buttons_to_enable_machine(&self) -> Option<Vec<Button>> {
         let mut nodes: HashMap<u16, Vec<Button>> = Default::default();
         nodes.insert(0, vec![]);
 
@@ -106,7 +97,7 @@ impl Machine {
 
                     let node_for_state = nodes.entry(new_state).or_insert_with(|| buttons);
 
-                    node_for_state.push(button.clone());
+                    node_for_state.push(*button);
 
                     if new_state == self.wanted_indicators.lights {
                         depth = Some(node_for_state.clone());
@@ -116,10 +107,13 @@ impl Machine {
             }
         }
 
-        depth.expect("Failed to find shortest way to target after 1000 iterations")
+        depth
     }
 
-    fn find_least_amount_of_buttons_to_configure_machine(&self) -> Vec<Button> {
+    /// Can search for a very long time, so it gives up once the part is cancelled.
+    pub fn find_least_amount_of_buttons_to_configure_machine(
+        &self,
+    ) -> Result<Option<Vec<Button>>, AocError> {
         let mut nodes: HashMap<Vec<u16>, Vec<Button>> = Default::default();
         nodes.insert(vec![0; self.joltages.len()], vec![]);
 
@@ -128,6 +122,8 @@ impl Machine {
         'search_loop: for _ in 0..1000 {
             // Iterate only 1000 times as failsafe.
             for (state, buttons) in nodes.clone().iter() {
+                cancel::check()?;
+
                 for button in &self.buttons {
                     let new_state: Vec<u16> = button.toggle_joltages(state);
 
@@ -139,7 +135,7 @@ impl Machine {
 
                     let node_for_state = nodes.entry(new_state.clone()).or_insert_with(|| buttons);
 
-                    node_for_state.push(button.clone());
+                    node_for_state.push(*button);
 
                     if self.compare_joltages(&new_state) {
                         depth = Some(node_for_state.clone());
@@ -149,10 +145,10 @@ impl Machine {
             }
         }
 
-        depth.expect("Failed to find shortest way to target after 1000 iterations")
+        Ok(depth)
     }
 
-    fn compare_joltages(&self, joltages: &Vec<u16>) -> bool {
+    fn compare_joltages(&self, joltages: &[u16]) -> bool {
         for (index, joltage) in self.joltages.iter().enumerate() {
             if joltage != &joltages[index] {
                 return false;
@@ -175,11 +171,32 @@ impl Display for Machine {
     }
 }
 
-struct IndicatorLights {
+pub struct IndicatorLights {
     amount_of_lights: u8,
     lights: u16,
 }
 
+impl IndicatorLights {
+    /// The lights that should be on, at most [MAX_LIGHTS] of them.
+    pub fn new(wanted: &[bool]) -> Result<IndicatorLights, AocError> {
+        if wanted.len() > MAX_LIGHTS {
+            return Err(AocError::InvalidInput(format!(
+                "a machine can have at most {MAX_LIGHTS} lights, found {}",
+                wanted.len()
+            )));
+        }
+
+        let lights = (0..wanted.len())
+            .filter(|index| wanted[*index])
+            .fold(0, |lights, index| lights | 1 << index);
+
+        Ok(IndicatorLights {
+            amount_of_lights: wanted.len() as u8,
+            lights,
+        })
+    }
+}
+
 impl Display for IndicatorLights {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_char('[')?;
@@ -201,11 +218,28 @@ impl Display for IndicatorLights {
 }
 
 #[derive(Debug, Copy, Clone)]
-struct Button {
+pub struct Button {
     toggles: u16,
 }
 
 impl Button {
+    /// The indices of the lights the button toggles, which are below [MAX_LIGHTS].
+    pub fn new(indices: &[usize]) -> Result<Button, AocError> {
+        let mut toggles = 0;
+        for index in indices {
+            if *index >= MAX_LIGHTS {
+                return Err(AocError::InvalidInput(format!(
+                    "a button can toggle lights 0 to {}, found {index}",
+                    MAX_LIGHTS - 1
+                )));
+            }
+
+            toggles |= 1 << index;
+        }
+
+        Ok(Button { toggles })
+    }
+
     fn toggle_indicators(&self, current_indicators: u16) -> u16 {
         current_indicators.bitxor(self.toggles)
     }
@@ -213,12 +247,12 @@ impl Button {
     fn toggle_joltages(&self, current_joltages: &[u16]) -> Vec<u16> {
         let mut new_joltages = Vec::from(current_joltages);
 
-        for i in 0..16 {
+        for (i, joltage) in new_joltages.iter_mut().enumerate() {
             let bit_to_check = 1 << i;
             let expected = self.toggles.bitand(bit_to_check);
 
             if expected == bit_to_check {
-                new_joltages[i] += 1;
+                *joltage += 1;
             }
         }
 
@@ -245,133 +279,184 @@ impl Display for Button {
     }
 }
 
-fn parse_machines(input: &str) -> Vec<Machine> {
-    input.lines().map(parse_machine).collect()
-}
+/// Indicators and buttons are stored as bits of a `u16`.
+pub const MAX_LIGHTS: usize = 16;
 
-fn parse_machine(input: &str) -> Machine {
+pub fn parse_machine(input: &str) -> Result<Machine, ParseError> {
     let button_index = input
         .find('(')
-        .unwrap_or_else(|| panic!("Could not find button section start '(' in {input}"));
+        .ok_or_else(|| ParseError::new(0, "could not find the button section start '('"))?;
     let joltage_index = input
         .find('{')
-        .unwrap_or_else(|| panic!("Could not find joltage section start '{{' in {input}"));
+        .filter(|joltage_index| *joltage_index > button_index)
+        .ok_or_else(|| {
+            ParseError::new(
+                button_index,
+                "could not find the joltage section start '{' after the buttons",
+            )
+        })?;
 
     let indicator_string = &input[..button_index];
     let button_string = &input[button_index..joltage_index];
     let joltage_string = &input[joltage_index..];
 
-    let indicators = parse_indicators(indicator_string);
-    let buttons = parse_buttons(button_string);
-    let joltages = parse_joltages(joltage_string);
+    let indicators = parse_indicators(indicator_string)?;
+    let buttons = parse_buttons(button_string).map_err(|error| error.shifted(button_index))?;
+    let joltages = parse_joltages(joltage_string).map_err(|error| error.shifted(joltage_index))?;
 
-    Machine {
+    Ok(Machine {
         wanted_indicators: indicators,
         buttons,
         joltages,
-    }
+    })
 }
 
-fn parse_indicators(input: &str) -> IndicatorLights {
-    let input = input.replace('[', "");
-    let input = input.replace(']', "");
-    let input = input.trim();
-
+fn parse_indicators(input: &str) -> Result<IndicatorLights, ParseError> {
     let mut indicators: u16 = 0;
     let mut amount = 0;
-    for (index, char) in input.chars().enumerate() {
+    for (offset, char) in input.char_indices() {
         match char {
+            '[' | ']' | ' ' => continue,
+            _ if amount == MAX_LIGHTS => {
+                return Err(ParseError::new(
+                    offset,
+                    format!("a machine can have at most {MAX_LIGHTS} lights"),
+                ));
+            }
             '.' => {}
             '#' => {
-                let bits = 1 << index;
+                let bits = 1 << amount;
                 indicators = indicators.add(bits)
             }
-            other => panic!("Unexpected character '{other}' in indicator input"),
+            other => {
+                return Err(ParseError::new(
+                    offset,
+                    format!("unexpected character '{other}' in the indicator lights"),
+                ));
+            }
         }
         amount += 1;
     }
 
-    IndicatorLights {
-        amount_of_lights: amount,
+    Ok(IndicatorLights {
+        amount_of_lights: amount as u8,
         lights: indicators,
-    }
+    })
 }
 
-fn parse_buttons(input: &str) -> Vec<Button> {
-    let input = input.trim();
+fn parse_buttons(input: &str) -> Result<Vec<Button>, ParseError> {
+    let mut buttons = Vec::new();
 
-    input.split(' ').map(parse_button).collect()
+    let mut offset = 0;
+    for button_text in input.trim_end().split(' ') {
+        buttons.push(parse_button(button_text).map_err(|error| error.shifted(offset))?);
+
+        offset += button_text.len() + 1;
+    }
+
+    Ok(buttons)
 }
 
-fn parse_button(input: &str) -> Button {
-    let input = input.replace('(', "");
-    let input = input.replace(')', "");
+fn parse_button(input: &str) -> Result<Button, ParseError> {
+    let mut toggles = 0;
+    for (offset, index) in parse_list::<usize>(input, '(', ')')? {
+        if index >= MAX_LIGHTS {
+            return Err(ParseError::new(
+                offset,
+                format!("a button can toggle lights 0 to {}", MAX_LIGHTS - 1),
+            ));
+        }
 
-    let toggles = input
-        .split(',')
-        .map(u8::from_str)
-        .map(|result| result.unwrap_or_else(|err| panic!("Failed parsing to u8: {err}")))
-        .map(|index| 1 << index)
-        .sum();
+        toggles |= 1 << index;
+    }
 
-    Button { toggles }
+    Ok(Button { toggles })
 }
 
-fn parse_joltages(input: &str) -> Vec<u16> {
-    let input = input.replace('{', "");
-    let input = input.replace('}', "");
-    let input = input.trim();
+fn parse_joltages(input: &str) -> Result<Vec<u16>, ParseError> {
+    let joltages = parse_list(input.trim_end(), '{', '}')?;
 
-    input
-        .split(',')
-        .map(u16::from_str)
-        .map(|result| result.unwrap_or_else(|err| panic!("Failed parsing to u8: {err}")))
-        .collect()
+    Ok(joltages.into_iter().map(|(_, joltage)| joltage).collect())
+}
+
+/// Parses a comma separated list between `open` and `close`, like `(1,3)`, along with the offset
+/// of every value.
+fn parse_list<T: FromStr>(
+    input: &str,
+    open: char,
+    close: char,
+) -> Result<Vec<(usize, T)>, ParseError> {
+    let values = input
+        .strip_prefix(open)
+        .and_then(|input| input.strip_suffix(close))
+        .ok_or_else(|| {
+            ParseError::new(
+                0,
+                format!("expected a list like '{open}1,3{close}', found '{input}'"),
+            )
+        })?;
+
+    let mut offset = open.len_utf8();
+    let mut parsed = Vec::new();
+    for value in values.split(',') {
+        let parsed_value = T::from_str(value)
+            .map_err(|_| ParseError::new(offset, format!("invalid number '{value}'")))?;
+        parsed.push((offset, parsed_value));
+
+        offset += value.len() + 1;
+    }
+
+    Ok(parsed)
 }
 
 #[test]
 fn test_toggle_machine() {
-    let button = parse_button("(3)");
+    let button = parse_button("(3)").unwrap();
     assert_eq!(button.toggle_indicators(0), 8);
 
-    let button = parse_button("(1,3)");
+    let button = parse_button("(1,3)").unwrap();
     assert_eq!(button.toggle_indicators(0), 10);
 
-    let button = parse_button("(2)");
+    let button = parse_button("(2)").unwrap();
     assert_eq!(button.toggle_indicators(0), 4);
 
-    let button = parse_button("(2,3)");
+    let button = parse_button("(2,3)").unwrap();
     assert_eq!(button.toggle_indicators(0), 12);
 
-    let button = parse_button("(0,2)");
+    let button = parse_button("(0,2)").unwrap();
     assert_eq!(button.toggle_indicators(0), 5);
 
-    let button = parse_button("(0,1)");
+    let button = parse_button("(0,1)").unwrap();
     assert_eq!(button.toggle_indicators(0), 3);
 }
 
 #[test]
 fn test_find_least_amount_of_buttons_to_enable_machines() {
-    let machine = parse_machine("[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}");
+    let machine = parse_machine("[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}").unwrap();
     assert_eq!(
         machine
             .find_least_amount_of_buttons_to_enable_machine()
+            .unwrap()
             .len(),
         2
     );
 
-    let machine = parse_machine("[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}");
+    let machine =
+        parse_machine("[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}").unwrap();
     assert_eq!(
         machine
             .find_least_amount_of_buttons_to_enable_machine()
+            .unwrap()
             .len(),
         3
     );
 
-    let machine = parse_machine("[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}");
+    let machine =
+        parse_machine("[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}").unwrap();
     assert_eq!(
         machine
             .find_least_amount_of_buttons_to_enable_machine()
+            .unwrap()
             .len(),
         2
     );
@@ -379,10 +464,12 @@ fn test_find_least_amount_of_buttons_to_enable_machines() {
 
 #[test]
 fn test_example_two() {
-    let machine = parse_machine("[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}");
+    let machine =
+        parse_machine("[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}").unwrap();
     assert_eq!(
         machine
             .find_least_amount_of_buttons_to_enable_machine()
+            .unwrap()
             .len(),
         3
     );
@@ -390,7 +477,8 @@ fn test_example_two() {
 
 #[test]
 fn test_example_manual() {
-    let machine = parse_machine("[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}");
+    let machine =
+        parse_machine("[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}").unwrap();
     let mut state = 0;
     state = machine.buttons[2].toggle_indicators(state);
     state = machine.buttons[3].toggle_indicators(state);
@@ -401,27 +489,71 @@ fn test_example_manual() {
 
 #[test]
 fn test_find_least_amount_of_buttons_to_configure_machines() {
-    let machine = parse_machine("[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}");
+    let machine = parse_machine("[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}").unwrap();
     assert_eq!(
         machine
             .find_least_amount_of_buttons_to_configure_machine()
+            .unwrap()
+            .unwrap()
             .len(),
         10
     );
 
-    let machine = parse_machine("[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}");
+    let machine =
+        parse_machine("[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}").unwrap();
     assert_eq!(
         machine
             .find_least_amount_of_buttons_to_configure_machine()
+            .unwrap()
+            .unwrap()
             .len(),
         12
     );
 
-    let machine = parse_machine("[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}");
+    let machine =
+        parse_machine("[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}").unwrap();
     assert_eq!(
         machine
             .find_least_amount_of_buttons_to_configure_machine()
+            .unwrap()
+            .unwrap()
             .len(),
         11
     );
 }
+
+#[test]
+fn test_configure_machine_is_cancelled() {
+    let machine = parse_machine("[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}").unwrap();
+
+    let result = cancel::with_timeout(Some(std::time::Duration::ZERO), || {
+        machine.find_least_amount_of_buttons_to_configure_machine()
+    });
+
+    assert!(matches!(result, Err(AocError::Cancelled)));
+}
+
+#[test]
+fn test_construct_machine() {
+    let machine = Machine {
+        wanted_indicators: IndicatorLights::new(&[false, true, true, false]).unwrap(),
+        buttons: [&[3][..], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]]
+            .into_iter()
+            .map(|indices| Button::new(indices).unwrap())
+            .collect(),
+        joltages: vec![3, 5, 4, 7],
+    };
+
+    let parsed = parse_machine("[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}").unwrap();
+
+    assert_eq!(machine.to_string(), parsed.to_string());
+    assert_eq!(
+        machine
+            .find_least_amount_of_buttons_to_enable_machine()
+            .unwrap()
+            .len(),
+        2
+    );
+    assert!(IndicatorLights::new(&[false; MAX_LIGHTS + 1]).is_err());
+    assert!(Button::new(&[MAX_LIGHTS]).is_err());
+}
@@ -1,90 +1,97 @@
-use crate::stopwatch::time;
+use crate::error::{AocError, ParseError};
+use crate::log::info;
+use crate::puzzle_input::PuzzleInput;
+use crate::solution::Solution;
+#[cfg(test)]
+use crate::input::{InputDirectory, InputKind};
+#[cfg(test)]
+use crate::solution::Part;
 use std::collections::{HashMap, HashSet};
-use std::fs::read_to_string;
 use std::hash::Hash;
 
-pub fn run() {
-    let example_data_1 = read_to_string("./puzzle-inputs/day-11-example-1.txt").unwrap_or_else(|_| {
-        panic!(
-            "Failed to read file {}",
-            "./puzzle-inputs/day-11-example-1.txt"
-        )
-    });
-    let example_data_2 = read_to_string("./puzzle-inputs/day-11-example-2.txt").unwrap_or_else(|_| {
-        panic!(
-            "Failed to read file {}",
-            "./puzzle-inputs/day-11-example-1.txt"
-        )
-    });
-    let puzzle_data = read_to_string("./puzzle-inputs/day-11-input.txt")
-        .unwrap_or_else(|_| panic!("Failed to read file {}", "./puzzle-inputs/day-11-input.txt"));
-
-    time("Day 11, Part 1 Example", || {
-        run_part_1(example_data_1.as_str());
-    });
-    time("Day 11, Part 1 Puzzle", || {
-        run_part_1(puzzle_data.as_str());
-    });
-
-    time("Day 11, Part 2 Example", || {
-        run_part_2(example_data_2.as_str());
-    });
-    time("Day 11, Part 2 Puzzle", || {
-        run_part_2(puzzle_data.as_str());
-    });
-}
+pub struct Day11;
 
-fn run_part_1(input: &str) -> u64 {
-    let graph = parse_graph(input);
+impl Solution for Day11 {
+    const DAY: u8 = 11;
 
-    let result = graph.count_paths_from_to(Box::from("you"), Box::from("out"));
+    type Input = Graph;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-    println!("The number of paths from `you` to `out` is {}", result);
+    fn parse(&self, input: &PuzzleInput) -> Result<Graph, AocError> {
+        parse_graph(input)
+    }
 
-    result
-}
+    fn part_1(&self, graph: &Graph) -> Result<u64, AocError> {
+        let result = graph.count_paths_from_to(Box::from("you"), Box::from("out"))?;
 
-fn run_part_2(input: &str) -> u64 {
-    let graph = parse_graph(input);
+        info!("The number of paths from `you` to `out` is {}", result);
 
-    let svr_to_fft = graph.find_paths_through(Box::from("svr"), Box::from("fft"));
-    let fft_to_dac = graph.find_paths_through(Box::from("fft"), Box::from("dac"));
-    let dac_to_out = graph.find_paths_through(Box::from("dac"), Box::from("out"));
+        Ok(result)
+    }
+
+    fn part_2(&self, graph: &Graph) -> Result<u64, AocError> {
+        let svr_to_fft = graph.find_paths_through(Box::from("svr"), Box::from("fft"))?;
+        let fft_to_dac = graph.find_paths_through(Box::from("fft"), Box::from("dac"))?;
+        let dac_to_out = graph.find_paths_through(Box::from("dac"), Box::from("out"))?;
 
-    let svr_to_dac = graph.find_paths_through(Box::from("svr"), Box::from("dac"));
-    let dac_to_fft = graph.find_paths_through(Box::from("dac"), Box::from("fft"));
-    let fft_to_out = graph.find_paths_through(Box::from("fft"), Box::from("out"));
+        let svr_to_dac = graph.find_paths_through(Box::from("svr"), Box::from("dac"))?;
+        let dac_to_fft = graph.find_paths_through(Box::from("dac"), Box::from("fft"))?;
+        let fft_to_out = graph.find_paths_through(Box::from("fft"), Box::from("out"))?;
 
-    let result = (svr_to_fft * fft_to_dac * dac_to_out) + (svr_to_dac * dac_to_fft * fft_to_out);
+        let result =
+            (svr_to_fft * fft_to_dac * dac_to_out) + (svr_to_dac * dac_to_fft * fft_to_out);
 
-    println!("The number of paths from `svr` to `out` through `fft` and `dac` is {}", result);
+        info!(
+            "The number of paths from `svr` to `out` through `fft` and `dac` is {}",
+            result
+        );
 
-    result
+        Ok(result)
+    }
 }
 
-struct Graph {
+pub struct Graph {
     nodes: HashMap<Box<str>, Node>,
 }
 
 impl Graph {
-    fn count_paths_from_to(&self, from: Box<str>, to: Box<str>) -> u64 {
+    /// Builds the graph from its devices, adding the `out` device every path ends at.
+    pub fn new(devices: Vec<Node>) -> Graph {
+        let mut nodes: HashMap<Box<str>, Node> = HashMap::new();
+
+        nodes.insert(
+            Box::from("out"),
+            Node {
+                identity: Box::from("out"),
+                destinations: vec![],
+            },
+        );
+
+        for node in devices {
+            nodes.insert(node.identity.clone(), node);
+        }
+
+        Graph { nodes }
+    }
+
+    fn node(&self, identity: &str) -> Result<&Node, AocError> {
+        self.nodes.get(identity).ok_or_else(|| AocError::Unsolvable(format!("Could not find node with identity '{}'", identity)))
+    }
+
+    pub fn count_paths_from_to(&self, from: Box<str>, to: Box<str>) -> Result<u64, AocError> {
         let mut nodes_to_visit: Vec<Box<str>> = Vec::new();
         let mut paths_from_root: HashMap<Box<str>, u64> = HashMap::new();
 
-        let end_node = self
-            .nodes
-            .get(&to)
-            .unwrap_or_else(|| panic!("Could not find node with identity '{}'", to));
+        let end_node = self.node(&to)?;
 
         nodes_to_visit.push(from.clone());
 
-        while nodes_to_visit.len() > 0 {
+        while !nodes_to_visit.is_empty() {
 
             let node_to_visit = nodes_to_visit.remove(nodes_to_visit.len() - 1);
 
-            let node_to_visit = self.nodes.get(&node_to_visit).unwrap_or_else(|| {
-                panic!("Could not find node with identity '{}'", node_to_visit)
-            });
+            let node_to_visit = self.node(&node_to_visit)?;
 
             let current_paths = paths_from_root.get(&node_to_visit.identity).unwrap_or(&0);
             paths_from_root.insert(
@@ -93,34 +100,32 @@ impl Graph {
             );
 
             for next_node in &node_to_visit.destinations {
-                let next_node = self.nodes.get(next_node).unwrap_or_else(|| {
-                    panic!("Could not find node with identity '{}'", next_node)
-                });
+                let next_node = self.node(next_node)?;
                 nodes_to_visit.push(next_node.identity.clone());
             }
         }
 
         paths_from_root
             .get(&end_node.identity)
-            .unwrap_or_else(|| panic!("Could not find any path from {} to {}", from, to))
-            .clone()
+            .copied()
+            .ok_or_else(|| AocError::Unsolvable(format!("Could not find any path from {} to {}", from, to)))
     }
 
-    fn find_paths_through(&self, from: Box<str>, to: Box<str>) -> u64 {
+    pub fn find_paths_through(&self, from: Box<str>, to: Box<str>) -> Result<u64, AocError> {
         self.dfs(&from, &to, &mut HashSet::new(), &mut HashMap::new())
     }
 
-    fn dfs(&self, from: &Box<str>, to: &Box<str>, visited: &mut HashSet<Box<str>>, memo: &mut HashMap<Box<str>, u64>) -> u64 {
+    fn dfs(&self, from: &str, to: &str, visited: &mut HashSet<Box<str>>, memo: &mut HashMap<Box<str>, u64>) -> Result<u64, AocError> {
         if memo.contains_key(from) {
-            return memo.get(from).unwrap_or(&0).clone();
+            return Ok(*memo.get(from).unwrap_or(&0));
         }
 
 
         if from == to {
-            return 1
+            return Ok(1)
         }
 
-        let from = self.nodes.get(from).unwrap_or_else(|| panic!("Could not find node with identity '{}'", from));
+        let from = self.node(from)?;
 
         visited.insert(from.identity.clone());
 
@@ -133,19 +138,19 @@ impl Graph {
             if visited.contains(child) {
                 continue;
             }
-            paths += self.dfs(child, to, visited, memo);
+            paths += self.dfs(child, to, visited, memo)?;
         }
 
         visited.remove(&from.identity.clone());
         memo.insert(from.identity.clone(), paths);
 
-        paths
+        Ok(paths)
     }
 }
 
-struct Node {
-    identity: Box<str>,
-    destinations: Vec<Box<str>>,
+pub struct Node {
+    pub identity: Box<str>,
+    pub destinations: Vec<Box<str>>,
 }
 
 impl Hash for Node {
@@ -154,33 +159,17 @@ impl Hash for Node {
     }
 }
 
-fn parse_graph(input: &str) -> Graph {
-    let mut nodes: HashMap<Box<str>, Node> = HashMap::new();
-
-    nodes.insert(
-        Box::from("out"),
-        Node {
-            identity: Box::from("out"),
-            destinations: vec![],
-        },
-    );
-
-    for line in input.lines() {
-        let node = read_and_insert_node(line);
-        nodes.insert(node.identity.clone(), node);
-    }
-
-    Graph { nodes }
+pub fn parse_graph(input: &PuzzleInput) -> Result<Graph, AocError> {
+    Ok(Graph::new(input.parse_lines(read_and_insert_node)?))
 }
 
-fn read_and_insert_node(line: &str) -> Node {
-    let mut split = line.split(':');
-    let header = split.next().unwrap_or_else(|| {
-        panic!("Invalid line. Could not find ':' and thus failed to parse header for '{line}'")
-    });
-    let values = split.next().unwrap_or_else(|| {
-        panic!("Invalid line. Could not find ':' and thus failed to parse values for '{line}'")
-    });
+pub fn read_and_insert_node(line: &str) -> Result<Node, ParseError> {
+    let (header, values) = line.split_once(':').ok_or_else(|| {
+        ParseError::new(line.len(), "could not find the ':' between the device and its outputs")
+    })?;
+    if header.is_empty() {
+        return Err(ParseError::new(0, "missing device name before ':'"));
+    }
 
     let destinations: Vec<Box<str>> = values
         .trim()
@@ -188,36 +177,37 @@ fn read_and_insert_node(line: &str) -> Node {
         .map(|destination| destination.to_owned().into_boxed_str())
         .collect();
 
-    Node {
+    Ok(Node {
         identity: Box::from(header),
         destinations,
-    }
+    })
 }
 
 #[test]
 fn test_example_1() {
-    let example_data = read_to_string("./puzzle-inputs/day-11-example-1.txt").unwrap_or_else(|_| {
-        panic!(
-            "Failed to read file {}",
-            "./puzzle-inputs/day-11-example-1.txt"
-        )
-    });
+    let example_data = InputDirectory::default().read(11, InputKind::Example(Part::One)).unwrap();
 
-    let result = run_part_1(example_data.as_str());
+    let result = Day11.part_1(&Day11.parse(&example_data).unwrap()).unwrap();
 
     assert_eq!(result, 5);
 }
 
 #[test]
 fn test_example_2() {
-    let example_data = read_to_string("./puzzle-inputs/day-11-example-2.txt").unwrap_or_else(|_| {
-        panic!(
-            "Failed to read file {}",
-            "./puzzle-inputs/day-11-example-2.txt"
-        )
-    });
+    let example_data = InputDirectory::default().read(11, InputKind::Example(Part::Two)).unwrap();
 
-    let result = run_part_2(example_data.as_str());
+    let result = Day11.part_2(&Day11.parse(&example_data).unwrap()).unwrap();
 
     assert_eq!(result, 2);
 }
+
+#[test]
+fn test_construct_graph() {
+    let node = |identity: &str, destinations: &[&str]| Node {
+        identity: Box::from(identity),
+        destinations: destinations.iter().map(|destination| Box::from(*destination)).collect(),
+    };
+    let graph = Graph::new(vec![node("you", &["a", "b"]), node("a", &["out"]), node("b", &["a", "out"])]);
+
+    assert_eq!(graph.count_paths_from_to(Box::from("you"), Box::from("out")).unwrap(), 3);
+}
@@ -1,99 +1,105 @@
-use crate::stopwatch::time;
-use std::fs::read_to_string;
-
-pub fn run() {
-    let example_data = read_to_string("./puzzle-inputs/day-2-example.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-2-example.txt", err
-        )
-    });
-    let puzzle_data = read_to_string("./puzzle-inputs/day-2-input.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-2-input.txt", err
-        )
-    });
+use crate::error::{AocError, ParseError};
+use crate::log::info;
+use crate::puzzle_input::PuzzleInput;
+use crate::solution::Solution;
 
-    time("Day 2, Part 1 Example", || {
-        run_part_1(example_data.as_str())
-    });
-    time("Day 2, Part 1 Puzzle", || run_part_1(puzzle_data.as_str()));
+pub struct Day2;
 
-    time("Day 2, Part 2 Example", || {
-        run_part_2(example_data.as_str())
-    });
-    time("Day 2, Part 2 Puzzle", || run_part_2(puzzle_data.as_str()));
-}
-
-struct IdRange {
-    first_id: u64,
-    last_id: u64,
-}
+impl Solution for Day2 {
+    const DAY: u8 = 2;
 
-fn read_id_ranges(input: &str) -> Vec<IdRange> {
-    let mut ids: Vec<IdRange> = Vec::new();
+    type Input = Vec<IdRange>;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-    for id_range_text in input.split(',') {
-        let split_at = id_range_text.find('-').unwrap_or_else(|| {
-            panic!(
-                "range of '{}' did not contain a `-` to split on",
-                id_range_text
-            )
-        });
-        let (first_id_text, last_id_text) = id_range_text.split_at(split_at);
-        let last_id_text = &last_id_text[1..last_id_text.len()]; // Remove the leading `-` from the start of the last_id_text
-
-        let range = IdRange {
-            first_id: first_id_text
-                .parse()
-                .unwrap_or_else(|e| panic!("Failed parsing id `{}`: {}", first_id_text, e)),
-            last_id: last_id_text
-                .parse()
-                .unwrap_or_else(|e| panic!("Failed parsing id `{}`: {}", last_id_text, e)),
-        };
+    fn parse(&self, input: &PuzzleInput) -> Result<Vec<IdRange>, AocError> {
+        let lines = input.parse_lines(read_id_ranges)?;
 
-        ids.push(range);
+        Ok(lines.into_iter().flatten().collect())
     }
 
-    ids
-}
+    fn part_1(&self, ranges: &Vec<IdRange>) -> Result<u64, AocError> {
+        let mut sum_of_invalid_ids: u64 = 0;
 
-fn run_part_1(input: &str) {
-    let mut sum_of_invalid_ids: u64 = 0;
+        for range in ranges.iter() {
+            for id in range.first_id..=range.last_id {
+                if has_twice_repeated_number_sequence(id) {
+                    sum_of_invalid_ids += id;
+                }
+            }
+        }
+
+        info!(
+            "The sum of all ids which are composed of a single repeated sequence is {}",
+            sum_of_invalid_ids
+        );
+
+        Ok(sum_of_invalid_ids)
+    }
+
+    fn part_2(&self, ranges: &Vec<IdRange>) -> Result<u64, AocError> {
+        let mut sum_of_invalid_ids: u64 = 0;
 
-    for range in read_id_ranges(input).iter() {
-        for id in range.first_id..=range.last_id {
-            if has_twice_repeated_number_sequence(id) {
-                sum_of_invalid_ids += id;
+        for range in ranges.iter() {
+            for id in range.first_id..=range.last_id {
+                if has_any_repeated_number_sequence(id) {
+                    sum_of_invalid_ids += id;
+                }
             }
         }
+
+        info!(
+            "The sum of all ids which are composed of multiple repeating sequences is {}",
+            sum_of_invalid_ids
+        );
+
+        Ok(sum_of_invalid_ids)
     }
+}
 
-    println!(
-        "The sum of all ids which are composed of a single repeated sequence is {}",
-        sum_of_invalid_ids
-    );
+pub struct IdRange {
+    pub first_id: u64,
+    pub last_id: u64,
 }
 
-fn run_part_2(input: &str) {
-    let mut sum_of_invalid_ids: u64 = 0;
+pub fn read_id_ranges(input: &str) -> Result<Vec<IdRange>, ParseError> {
+    let mut ids: Vec<IdRange> = Vec::new();
 
-    for range in read_id_ranges(input).iter() {
-        for id in range.first_id..=range.last_id {
-            if has_any_repeated_number_sequence(id) {
-                sum_of_invalid_ids += id;
-            }
-        }
+    let mut offset = 0;
+    for id_range_text in input.split(',') {
+        let range = read_id_range(id_range_text).map_err(|error| error.shifted(offset))?;
+        ids.push(range);
+
+        offset += id_range_text.len() + 1;
     }
 
-    println!(
-        "The sum of all ids which are composed of multiple repeating sequences is {}",
-        sum_of_invalid_ids
-    );
+    Ok(ids)
 }
 
-fn has_twice_repeated_number_sequence(id: u64) -> bool {
+pub fn read_id_range(id_range_text: &str) -> Result<IdRange, ParseError> {
+    let split_at = id_range_text.find('-').ok_or_else(|| {
+        ParseError::new(
+            0,
+            format!("range of '{id_range_text}' did not contain a `-` to split on"),
+        )
+    })?;
+    let (first_id_text, last_id_text) = id_range_text.split_at(split_at);
+    let last_id_text = &last_id_text[1..]; // Remove the leading `-` from the start of the last_id_text
+
+    let parse_id = |text: &str, offset: usize| {
+        text.parse::<u64>()
+            .ok()
+            .filter(|id| *id > 0)
+            .ok_or_else(|| ParseError::new(offset, format!("invalid id '{text}'")))
+    };
+
+    Ok(IdRange {
+        first_id: parse_id(first_id_text, 0)?,
+        last_id: parse_id(last_id_text, split_at + 1)?,
+    })
+}
+
+pub fn has_twice_repeated_number_sequence(id: u64) -> bool {
     // 1. Find the logarithmic size of the id
     // 2. If even, split the number into two parts.
     // 3. Compare the two parts.
@@ -111,7 +117,7 @@ fn has_twice_repeated_number_sequence(id: u64) -> bool {
     high_part == low_part
 }
 
-fn has_any_repeated_number_sequence(id: u64) -> bool {
+pub fn has_any_repeated_number_sequence(id: u64) -> bool {
     let digits_in_id = id.ilog10() + 1;
     let max_group_size = digits_in_id / 2;
 
@@ -294,3 +300,16 @@ fn show_calculate_shifting_decimals() {
         2
     );
 }
+
+#[test]
+fn test_read_id_ranges_points_at_invalid_id() {
+    assert_eq!(read_id_ranges("11-22,95-115").map(|ids| ids.len()), Ok(2));
+    assert_eq!(
+        read_id_ranges("11-22,95-x").err(),
+        Some(ParseError::new(9, "invalid id 'x'"))
+    );
+    assert_eq!(
+        read_id_ranges("11-22,9515").err().map(|e| e.offset),
+        Some(6)
+    );
+}
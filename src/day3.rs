@@ -1,82 +1,81 @@
-use crate::stopwatch::time;
-use std::fs::read_to_string;
+use crate::error::{AocError, ParseError};
+use crate::log::info;
+use crate::puzzle_input::PuzzleInput;
+use crate::solution::Solution;
 use std::str::FromStr;
 
-pub fn run() {
-    let example_data = read_to_string("./puzzle-inputs/day-3-example.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-3-example.txt", err
-        )
-    });
-    let puzzle_data = read_to_string("./puzzle-inputs/day-3-input.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-3-input.txt", err
-        )
-    });
-
-    time("Day 3, Part 1 Example", || {
-        run_part_1(example_data.as_str())
-    });
-    time("Day 3, Part 1 Puzzle", || run_part_1(puzzle_data.as_str()));
-
-    time("Day 3, Part 2 Example", || {
-        run_part_2(example_data.as_str())
-    });
-    time("Day 3, Part 2 Puzzle", || run_part_2(puzzle_data.as_str()));
-}
+pub struct Day3;
 
-struct Bank {
-    // Joltage is not a typo. It's part of the AoC **domain**.
-    battery_joltages: Vec<u8>,
-}
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Vec<Bank>;
+    type Answer1 = u16;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &PuzzleInput) -> Result<Vec<Bank>, AocError> {
+        input.parse_lines(parse_bank)
+    }
+
+    fn part_1(&self, banks: &Vec<Bank>) -> Result<u16, AocError> {
+        let mut total_output_joltage = 0_u16;
 
-fn run_part_1(input: &str) {
-    let banks = parse_banks(input);
+        for bank in banks {
+            check_bank_size(bank, 2)?;
+            total_output_joltage += find_highest_joltage_combined_from_n_batteries(bank, 2) as u16;
+        }
 
-    let mut total_output_joltage = 0_u16;
+        info!("The total output joltage is {}", total_output_joltage);
 
-    for bank in banks {
-        total_output_joltage += find_highest_joltage_combined_from_n_batteries(&bank, 2) as u16;
+        Ok(total_output_joltage)
     }
 
-    println!("The total output joltage is {}", total_output_joltage);
-}
+    fn part_2(&self, banks: &Vec<Bank>) -> Result<u64, AocError> {
+        let mut total_output_joltage: u64 = 0;
 
-fn run_part_2(input: &str) {
-    let banks = parse_banks(input);
+        for bank in banks {
+            check_bank_size(bank, 12)?;
+            total_output_joltage += find_highest_joltage_combined_from_n_batteries(bank, 12);
+        }
 
-    let mut total_output_joltage: u64 = 0;
+        info!("The total output joltage is {}", total_output_joltage);
 
-    for bank in banks {
-        total_output_joltage += find_highest_joltage_combined_from_n_batteries(&bank, 12);
+        Ok(total_output_joltage)
     }
+}
 
-    println!("The total output joltage is {}", total_output_joltage);
+pub struct Bank {
+    // Joltage is not a typo. It's part of the AoC **domain**.
+    pub battery_joltages: Vec<u8>,
 }
 
-fn parse_banks(input: &str) -> Vec<Bank> {
-    let mut banks: Vec<Bank> = Vec::new();
+pub fn parse_bank(bank_string: &str) -> Result<Bank, ParseError> {
+    let mut bank = Bank {
+        battery_joltages: Vec::new(),
+    };
+    for (offset, joltage_text) in bank_string.char_indices() {
+        let joltage: u8 = u8::from_str(joltage_text.to_string().as_str())
+            .map_err(|_| ParseError::new(offset, format!("invalid joltage '{joltage_text}'")))?;
 
-    for bank_string in input.lines() {
-        let mut bank = Bank {
-            battery_joltages: Vec::new(),
-        };
-        for joltage_text in bank_string.chars() {
-            let joltage: u8 = u8::from_str(joltage_text.to_string().as_str())
-                .unwrap_or_else(|e| panic!("Failed parsing Joltage {}: {}", joltage_text, e));
+        bank.battery_joltages.push(joltage)
+    }
 
-            bank.battery_joltages.push(joltage)
-        }
+    Ok(bank)
+}
 
-        banks.push(bank);
+pub fn check_bank_size(bank: &Bank, amount_of_batteries_to_combine: usize) -> Result<(), AocError> {
+    if bank.battery_joltages.len() < amount_of_batteries_to_combine {
+        return Err(AocError::Unsolvable(format!(
+            "a bank of {} batteries cannot combine {amount_of_batteries_to_combine} of them",
+            bank.battery_joltages.len()
+        )));
     }
 
-    banks
+    Ok(())
 }
 
-fn find_highest_joltage_combined_from_n_batteries(
+/// The bank needs at least as many batteries as are combined, see [check_bank_size].
+pub fn find_highest_joltage_combined_from_n_batteries(
     bank: &Bank,
     amount_of_batteries_to_combine: u8,
 ) -> u64 {
@@ -112,13 +111,13 @@ fn find_highest_joltage_combined_from_n_batteries(
 }
 
 #[test]
-fn test_parse_banks() {
+fn test_parse_bank() {
     assert_eq!(
-        parse_banks("987654321111111")[0].battery_joltages,
+        parse_bank("987654321111111").unwrap().battery_joltages,
         vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 1, 1, 1, 1, 1, 1]
     );
     assert_eq!(
-        parse_banks("811111111111119")[0].battery_joltages,
+        parse_bank("811111111111119").unwrap().battery_joltages,
         vec![8, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 9]
     );
 }
@@ -126,7 +125,7 @@ fn test_parse_banks() {
 #[test]
 fn test_find_highest_joltage_from_n_batteries() {
     assert_eq!(
-        find_highest_joltage_combined_from_n_batteries(&parse_banks("81191")[0], 3),
+        find_highest_joltage_combined_from_n_batteries(&parse_bank("81191").unwrap(), 3),
         891
     );
 }
@@ -134,19 +133,19 @@ fn test_find_highest_joltage_from_n_batteries() {
 #[test]
 fn test_find_highest_joltage_from_two_batteries() {
     assert_eq!(
-        find_highest_joltage_combined_from_n_batteries(&parse_banks("987654321111111")[0], 2),
+        find_highest_joltage_combined_from_n_batteries(&parse_bank("987654321111111").unwrap(), 2),
         98
     );
     assert_eq!(
-        find_highest_joltage_combined_from_n_batteries(&parse_banks("811111111111119")[0], 2),
+        find_highest_joltage_combined_from_n_batteries(&parse_bank("811111111111119").unwrap(), 2),
         89
     );
     assert_eq!(
-        find_highest_joltage_combined_from_n_batteries(&parse_banks("234234234234278")[0], 2),
+        find_highest_joltage_combined_from_n_batteries(&parse_bank("234234234234278").unwrap(), 2),
         78
     );
     assert_eq!(
-        find_highest_joltage_combined_from_n_batteries(&parse_banks("818181911112111")[0], 2),
+        find_highest_joltage_combined_from_n_batteries(&parse_bank("818181911112111").unwrap(), 2),
         92
     );
 }
@@ -154,19 +153,27 @@ fn test_find_highest_joltage_from_two_batteries() {
 #[test]
 fn test_find_highest_joltage_combined_from_12_batteries() {
     assert_eq!(
-        find_highest_joltage_combined_from_n_batteries(&parse_banks("987654321111111")[0], 12),
+        find_highest_joltage_combined_from_n_batteries(&parse_bank("987654321111111").unwrap(), 12),
         987654321111
     );
     assert_eq!(
-        find_highest_joltage_combined_from_n_batteries(&parse_banks("811111111111119")[0], 12),
+        find_highest_joltage_combined_from_n_batteries(&parse_bank("811111111111119").unwrap(), 12),
         811111111119
     );
     assert_eq!(
-        find_highest_joltage_combined_from_n_batteries(&parse_banks("234234234234278")[0], 12),
+        find_highest_joltage_combined_from_n_batteries(&parse_bank("234234234234278").unwrap(), 12),
         434234234278
     );
     assert_eq!(
-        find_highest_joltage_combined_from_n_batteries(&parse_banks("818181911112111")[0], 12),
+        find_highest_joltage_combined_from_n_batteries(&parse_bank("818181911112111").unwrap(), 12),
         888911112111
     );
 }
+
+#[test]
+fn test_parse_bank_points_at_invalid_joltage() {
+    assert_eq!(
+        parse_bank("98x1").err(),
+        Some(ParseError::new(2, "invalid joltage 'x'"))
+    );
+}
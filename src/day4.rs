@@ -1,86 +1,161 @@
-use std::collections::HashSet;
-use std::fs::read_to_string;
-
-use crate::stopwatch::time;
-
-pub fn run() {
-    let example_data = read_to_string("./puzzle-inputs/day-4-example.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-4-example.txt", err
-        )
-    });
-    let puzzle_data = read_to_string("./puzzle-inputs/day-4-input.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-4-input.txt", err
-        )
-    });
-
-    time("Day 4, Part 1 Example", || {
-        run_part_1(example_data.as_str())
-    });
-    time("Day 4, Part 1 Puzzle", || run_part_1(puzzle_data.as_str()));
-
-    time("Day 4, Part 2 Example", || {
-        run_part_2(example_data.as_str())
-    });
-    time("Day 4, Part 2 Puzzle", || run_part_2(puzzle_data.as_str()));
-}
+use std::collections::{HashMap, HashSet};
 
-fn run_part_1(input: &str) {
-    let roll_positions = parse_roll_positions(input);
+use crate::error::AocError;
+use crate::export::{self, Picture};
+use crate::log::{debug, info};
+use crate::puzzle_input::PuzzleInput;
+use crate::solution::Solution;
+use crate::visualize::{self, Color, Frame};
 
-    let rolls = find_rolls_with_less_than_4_neighbours(&roll_positions);
+pub struct Day4;
 
-    println!(
-        "The number of paper rolls with less than 4 neighbours is {}",
-        rolls.len()
-    );
-}
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = HashSet<(u8, u8)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-fn run_part_2(input: &str) {
-    let mut roll_positions = parse_roll_positions(input);
-    let mut removed_rolls = 0;
+    fn parse(&self, input: &PuzzleInput) -> Result<HashSet<(u8, u8)>, AocError> {
+        parse_roll_positions(input)
+    }
 
-    loop {
-        let removable_rolls = find_rolls_with_less_than_4_neighbours(&roll_positions);
+    fn part_1(&self, roll_positions: &HashSet<(u8, u8)>) -> Result<usize, AocError> {
+        let rolls = find_rolls_with_less_than_4_neighbours(roll_positions);
 
-        if removable_rolls.is_empty() {
-            println!("No more rolls can be removed.");
-            break;
-        }
+        info!(
+            "The number of paper rolls with less than 4 neighbours is {}",
+            rolls.len()
+        );
 
-        removed_rolls += removable_rolls.len();
+        Ok(rolls.len())
+    }
 
-        for roll in &removable_rolls {
-            roll_positions.retain(|position| position != roll);
+    fn part_2(&self, roll_positions: &HashSet<(u8, u8)>) -> Result<usize, AocError> {
+        let (width, height) = grid_size(roll_positions);
+        let mut roll_positions = roll_positions.clone();
+        let mut removed_rolls = 0;
+        let mut removal_rounds: HashMap<(u8, u8), usize> = HashMap::new();
+
+        for round in 1.. {
+            let removable_rolls = find_rolls_with_less_than_4_neighbours(&roll_positions);
+            visualize::emit(|| {
+                removal_frame(round, (width, height), &roll_positions, &removable_rolls)
+            });
+
+            if removable_rolls.is_empty() {
+                debug!("No more rolls can be removed.");
+                break;
+            }
+
+            removed_rolls += removable_rolls.len();
+            if export::recording() {
+                removal_rounds.extend(removable_rolls.iter().map(|roll| (*roll, round)));
+            }
+
+            for roll in &removable_rolls {
+                roll_positions.retain(|position| position != roll);
+            }
+
+            debug!("Removed {} rolls this iteration", removable_rolls.len());
         }
 
-        println!("Removed {} rolls this iteration", removable_rolls.len());
+        export::emit("rolls-by-round", || {
+            Picture::Grid(rounds_frame(
+                (width, height),
+                &roll_positions,
+                &removal_rounds,
+            ))
+        });
+
+        info!(
+            "The of rolls that can be recursively removed is {}",
+            removed_rolls
+        );
+
+        Ok(removed_rolls)
+    }
+}
+
+fn grid_size(positions: &HashSet<(u8, u8)>) -> (usize, usize) {
+    let width = positions.iter().map(|(x, _)| *x as usize + 1).max();
+    let height = positions.iter().map(|(_, y)| *y as usize + 1).max();
+
+    (width.unwrap_or(0), height.unwrap_or(0))
+}
+
+/// The rolls that are left, with the ones removed this round in red.
+fn removal_frame(
+    round: usize,
+    (width, height): (usize, usize),
+    positions: &HashSet<(u8, u8)>,
+    removable: &HashSet<(u8, u8)>,
+) -> Frame {
+    let mut frame = Frame::new(
+        format!("Round {round}: removing {} rolls", removable.len()),
+        width,
+        height,
+    );
+    for (x, y) in positions {
+        let color = if removable.contains(&(*x, *y)) {
+            Color::Red
+        } else {
+            Color::White
+        };
+        frame.set(*x as usize, *y as usize, '@', color);
     }
 
-    println!(
-        "The of rolls that can be recursively removed is {}",
-        removed_rolls
+    frame
+}
+
+/// Every roll colored by the round it was removed in, from red for the first round to blue for the
+/// last, and the rolls that stay in white.
+fn rounds_frame(
+    (width, height): (usize, usize),
+    remaining: &HashSet<(u8, u8)>,
+    removal_rounds: &HashMap<(u8, u8), usize>,
+) -> Frame {
+    let last_round = removal_rounds.values().copied().max().unwrap_or(1);
+    let mut frame = Frame::new(
+        format!("Rolls removed over {last_round} rounds"),
+        width,
+        height,
     );
+
+    for ((x, y), round) in removal_rounds {
+        let fraction = (*round - 1) as f64 / (last_round.max(2) - 1) as f64;
+        let color = Color::blend(Color::Red, Color::Blue, fraction);
+        frame.set(*x as usize, *y as usize, '@', color);
+    }
+    for (x, y) in remaining {
+        frame.set(*x as usize, *y as usize, '@', Color::White);
+    }
+
+    frame
 }
 
-fn parse_roll_positions(input: &str) -> HashSet<(u8, u8)> {
+pub fn parse_roll_positions(input: &PuzzleInput) -> Result<HashSet<(u8, u8)>, AocError> {
     let mut positions: HashSet<(u8, u8)> = HashSet::new();
 
-    for (y, row) in input.lines().enumerate() {
-        for (x, column) in row.chars().enumerate() {
-            if column == '@' {
-                positions.insert((x as u8, y as u8));
-            }
+    for (x, y, cell) in input.grid().cells() {
+        match cell {
+            '@' => match (u8::try_from(x), u8::try_from(y)) {
+                (Ok(x), Ok(y)) => {
+                    positions.insert((x, y));
+                }
+                _ => {
+                    return Err(input.cell_error(x, y, "the grid can be at most 256 by 256 cells"));
+                }
+            },
+            '.' => {}
+            other => return Err(input.cell_error(x, y, format!("unexpected character '{other}'"))),
         }
     }
 
-    positions
+    Ok(positions)
 }
 
-fn find_rolls_with_less_than_4_neighbours(positions: &HashSet<(u8, u8)>) -> HashSet<(u8, u8)> {
+pub fn find_rolls_with_less_than_4_neighbours(positions: &HashSet<(u8, u8)>) -> HashSet<(u8, u8)> {
     let mut removable_roll_positions: HashSet<(u8, u8)> = HashSet::new();
 
     for position in positions {
@@ -1,79 +1,78 @@
-use crate::stopwatch::time;
+use crate::error::{AocError, ParseError};
+use crate::log::info;
+use crate::puzzle_input::PuzzleInput;
+use crate::solution::Solution;
 use std::cmp::{max, min};
 use std::fmt::{Display, Formatter};
-use std::fs::read_to_string;
 
-pub fn run() {
-    let example_data = read_to_string("./puzzle-inputs/day-5-example.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-5-example.txt", err
-        )
-    });
-    let puzzle_data = read_to_string("./puzzle-inputs/day-5-input.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-5-input.txt", err
-        )
-    });
+pub struct Day5;
 
-    time("Day 5, Part 1 Example", || {
-        run_part_1(example_data.as_str())
-    });
-    time("Day 5, Part 1 Puzzle", || run_part_1(puzzle_data.as_str()));
+impl Solution for Day5 {
+    const DAY: u8 = 5;
 
-    time("Day 5, Part 2 Example", || {
-        run_part_2(example_data.as_str())
-    });
-    time("Day 5, Part 2 Puzzle", || run_part_2(puzzle_data.as_str()));
-}
+    type Input = Inventory;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-fn run_part_1(input: &str) {
-    let sections = input.replace("\r", "");
-    let mut sections = sections.split("\n\n");
+    fn parse(&self, input: &PuzzleInput) -> Result<Inventory, AocError> {
+        let mut sections = input.sections();
 
-    let ranges_text = sections.next().expect("No ranges section found in input");
-    let ids_text = sections.next().expect("No ids section found in input");
+        let ranges = sections
+            .next()
+            .ok_or_else(|| AocError::InvalidInput("no ranges section found".to_owned()))?;
+        let ids = sections.next().ok_or_else(|| {
+            AocError::InvalidInput("no ids section found after the ranges".to_owned())
+        })?;
 
-    let ranges = read_ingredient_ranges(ranges_text);
-    let ingredients = read_ingredients(ids_text);
+        Ok(Inventory {
+            ranges: ranges.parse_lines(read_ingredient_range)?,
+            ingredients: ids.parse_lines(read_ingredient)?,
+        })
+    }
 
-    let mut total_fresh_ingredients = 0;
+    fn part_1(&self, inventory: &Inventory) -> Result<u64, AocError> {
+        let mut total_fresh_ingredients = 0;
 
-    'ingredient_id_iterator: for ingredient_id in ingredients {
-        for range in &ranges {
-            if range.includes(ingredient_id) {
-                total_fresh_ingredients += 1;
-                continue 'ingredient_id_iterator;
+        'ingredient_id_iterator: for ingredient_id in &inventory.ingredients {
+            for range in &inventory.ranges {
+                if range.includes(*ingredient_id) {
+                    total_fresh_ingredients += 1;
+                    continue 'ingredient_id_iterator;
+                }
             }
         }
+
+        info!(
+            "The total number of fresh ingredients is {}",
+            total_fresh_ingredients
+        );
+
+        Ok(total_fresh_ingredients)
     }
 
-    println!(
-        "The total number of fresh ingredients is {}",
-        total_fresh_ingredients
-    );
-}
+    fn part_2(&self, inventory: &Inventory) -> Result<u64, AocError> {
+        let merged_ranges = merge_ranges(inventory.ranges.clone());
 
-fn run_part_2(input: &str) {
-    let mut sections = input.split("\r\n\r\n");
-    let ranges_text = sections.next().expect("No ranges section found in input");
+        let mut total = 0;
+        for range in merged_ranges {
+            total += range.size()
+        }
 
-    let ranges = read_ingredient_ranges(ranges_text);
-    let merged_ranges = merge_ranges(ranges);
+        info!("The total number of fresh ingredients is {}", total);
 
-    let mut total = 0;
-    for range in merged_ranges {
-        total += range.size()
+        Ok(total)
     }
+}
 
-    println!("The total number of fresh ingredients is {}", total);
+pub struct Inventory {
+    pub ranges: Vec<IngredientRange>,
+    pub ingredients: Vec<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
-struct IngredientRange {
-    first_id: u64,
-    last_id: u64,
+pub struct IngredientRange {
+    pub first_id: u64,
+    pub last_id: u64,
 }
 
 impl IngredientRange {
@@ -99,59 +98,44 @@ impl Display for IngredientRange {
     }
 }
 
-fn read_ingredient_ranges(input: &str) -> Vec<IngredientRange> {
-    let mut ids: Vec<IngredientRange> = Vec::new();
-
-    for id_range_text in input.lines() {
-        if id_range_text.is_empty() {
-            break;
-        }
-
-        let split_at = id_range_text.find('-').unwrap_or_else(|| {
-            panic!(
-                "range of '{}' did not contain a `-` to split on",
-                id_range_text
-            )
-        });
-        let (first_id_text, last_id_text) = id_range_text.split_at(split_at);
-        let last_id_text = &last_id_text[1..last_id_text.len()]; // Remove the leading `-` from the start of the last_id_text
-
-        let range = IngredientRange {
-            first_id: first_id_text
-                .parse()
-                .unwrap_or_else(|e| panic!("Failed parsing id `{}`: {}", first_id_text, e)),
-            last_id: last_id_text
-                .parse()
-                .unwrap_or_else(|e| panic!("Failed parsing id `{}`: {}", last_id_text, e)),
-        };
-
-        ids.push(range);
+pub fn read_ingredient_range(id_range_text: &str) -> Result<IngredientRange, ParseError> {
+    let split_at = id_range_text.find('-').ok_or_else(|| {
+        ParseError::new(
+            0,
+            format!("range of '{id_range_text}' did not contain a `-` to split on"),
+        )
+    })?;
+    let (first_id_text, last_id_text) = id_range_text.split_at(split_at);
+    let last_id_text = &last_id_text[1..]; // Remove the leading `-` from the start of the last_id_text
+
+    let first_id = read_ingredient(first_id_text)?;
+    let last_id = read_ingredient(last_id_text).map_err(|error| error.shifted(split_at + 1))?;
+    if last_id < first_id {
+        return Err(ParseError::new(
+            split_at + 1,
+            format!("range ends at {last_id}, before its start {first_id}"),
+        ));
     }
 
-    ids
+    Ok(IngredientRange { first_id, last_id })
 }
 
-fn read_ingredients(input: &str) -> Vec<u64> {
-    input
-        .lines()
-        .map(|id_text| {
-            id_text
-                .parse()
-                .unwrap_or_else(|e| panic!("Failed parsing id `{}`: {}", id_text, e))
-        })
-        .collect()
+pub fn read_ingredient(id_text: &str) -> Result<u64, ParseError> {
+    id_text
+        .parse()
+        .map_err(|_| ParseError::new(0, format!("invalid id '{id_text}'")))
 }
 
-fn merge_ranges(mut ranges: Vec<IngredientRange>) -> Vec<IngredientRange> {
+pub fn merge_ranges(mut ranges: Vec<IngredientRange>) -> Vec<IngredientRange> {
     let mut merged_ranges = Vec::new();
 
     ranges.sort_unstable_by(|a, b| a.first_id.cmp(&b.first_id).then(a.last_id.cmp(&b.last_id)));
 
     let mut range_iterator = ranges.iter();
-    let mut current_range = range_iterator
-        .next()
-        .expect("Range did not have a first element")
-        .clone();
+    let Some(first_range) = range_iterator.next() else {
+        return merged_ranges;
+    };
+    let mut current_range = first_range.clone();
     let mut next_range = range_iterator.next();
 
     while next_range.is_some() {
@@ -420,3 +404,8 @@ fn test_range_size() {
         "Has correct size"
     );
 }
+
+#[test]
+fn test_merge_no_ranges() {
+    assert!(merge_ranges(Vec::new()).is_empty());
+}
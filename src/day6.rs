@@ -1,82 +1,102 @@
-use crate::stopwatch::time;
-use std::fs::read_to_string;
+use crate::error::{AocError, ParseError};
+#[cfg(test)]
+use crate::input::{InputDirectory, InputKind};
+use crate::log::info;
+use crate::puzzle_input::PuzzleInput;
+#[cfg(test)]
+use crate::solution::Part;
+use crate::solution::Solution;
 use std::str::FromStr;
 
-pub fn run() {
-    let example_data = read_to_string("./puzzle-inputs/day-6-example.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-6-example.txt", err
-        )
-    });
-    let puzzle_data = read_to_string("./puzzle-inputs/day-6-input.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-6-input.txt", err
-        )
-    });
-
-    time("Day 6, Part 1 Example", || {
-        run_part_1(example_data.as_str());
-    });
-    time("Day 6, Part 1 Puzzle", || {
-        run_part_1(puzzle_data.as_str());
-    });
-
-    time("Day 6, Part 2 Example", || {
-        run_part_2(example_data.as_str());
-    });
-    time("Day 6, Part 2 Puzzle", || {
-        run_part_2(puzzle_data.as_str());
-    });
-}
+pub struct Day6;
 
-fn run_part_1(input: &str) -> u64 {
-    let input = input.replace("\r", "");
-    let split_numbers: Vec<&str> = input.split("\n").collect();
+impl Solution for Day6 {
+    const DAY: u8 = 6;
 
-    let number_rows = &split_numbers[0..split_numbers.len() - 1];
-    let operand_row = &split_numbers[split_numbers.len() - 1..split_numbers.len()][0];
+    type Input = Vec<WorksheetColumn>;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-    let worksheet_columns: Vec<WorksheetColumn> = parse_worklist_columns(number_rows, operand_row);
+    fn parse(&self, input: &PuzzleInput) -> Result<Vec<WorksheetColumn>, AocError> {
+        let split_numbers: Vec<&str> = input.lines().collect();
 
-    let result = worksheet_columns
-        .iter()
-        .map(|worklist_column| worklist_column.calculate_horizontal())
-        .sum();
+        let Some((operand_row, number_rows)) = split_numbers.split_last() else {
+            return Err(AocError::InvalidInput("the worksheet is empty".to_owned()));
+        };
+
+        for (line_index, row) in number_rows.iter().enumerate() {
+            let unexpected = row
+                .char_indices()
+                .find(|(_, character)| !character.is_ascii_digit() && *character != ' ');
+            if let Some((offset, character)) = unexpected {
+                let error = ParseError::new(offset, format!("unexpected character '{character}'"));
+                return Err(error.at_line(line_index + 1, row));
+            }
+        }
 
-    println!("The sum of all worksheet items is {}", result);
+        let operands = parse_operands(operand_row)
+            .map_err(|error| error.at_line(split_numbers.len(), operand_row))?;
 
-    result
-}
+        parse_worklist_columns(number_rows, operands)
+    }
+
+    fn part_1(&self, worksheet_columns: &Vec<WorksheetColumn>) -> Result<u64, AocError> {
+        let result = worksheet_columns
+            .iter()
+            .map(|worklist_column| worklist_column.calculate_horizontal())
+            .sum::<Result<u64, AocError>>()?;
 
-fn run_part_2(input: &str) -> u64 {
-    let input = input.replace("\r", "");
-    let split_numbers: Vec<&str> = input.split("\n").collect();
+        info!("The sum of all worksheet items is {}", result);
 
-    let number_rows = &split_numbers[0..split_numbers.len() - 1];
-    let operand_row = &split_numbers[split_numbers.len() - 1..split_numbers.len()][0];
+        Ok(result)
+    }
 
-    let worksheet_columns: Vec<WorksheetColumn> = parse_worklist_columns(number_rows, operand_row);
+    fn part_2(&self, worksheet_columns: &Vec<WorksheetColumn>) -> Result<u64, AocError> {
+        let result = worksheet_columns
+            .iter()
+            .map(|worklist_column| worklist_column.calculate_vertical())
+            .sum::<Result<u64, AocError>>()?;
 
-    let result = worksheet_columns
-        .iter()
-        .map(|worklist_column| worklist_column.calculate_vertical())
-        .sum();
+        info!("The sum of all worksheet items is {}", result);
 
-    println!("The sum of all worksheet items is {}", result);
+        Ok(result)
+    }
+}
+
+pub fn parse_operands(operand_row: &str) -> Result<Vec<Operand>, ParseError> {
+    let mut operands = Vec::new();
+
+    let mut offset = 0;
+    for operand_text in operand_row.split(' ') {
+        match operand_text {
+            "" => {}
+            "+" => operands.push(Operand::Addition),
+            "*" => operands.push(Operand::Multiply),
+            other => {
+                return Err(ParseError::new(
+                    offset,
+                    format!("invalid operand '{other}', expected '+' or '*'"),
+                ));
+            }
+        }
+
+        offset += operand_text.len() + 1;
+    }
 
-    result
+    Ok(operands)
 }
 
-fn parse_worklist_columns(number_rows: &[&str], operand_row: &str) -> Vec<WorksheetColumn> {
+pub fn parse_worklist_columns(
+    number_rows: &[&str],
+    operands: Vec<Operand>,
+) -> Result<Vec<WorksheetColumn>, AocError> {
     let mut worksheets: Vec<WorksheetColumn> = Vec::new();
 
     let number_rows: Vec<Vec<char>> = number_rows
         .iter()
         .map(|row| row.chars().collect())
         .collect();
-    let mut operand_iterator = operand_row.split_whitespace();
+    let mut operand_iterator = operands.into_iter();
     let mut buffer: Vec<String> = vec!["".to_owned(); number_rows.len()];
 
     let total_row_length = number_rows.iter().map(|row| row.len()).max().unwrap_or(0);
@@ -91,12 +111,12 @@ fn parse_worklist_columns(number_rows: &[&str], operand_row: &str) -> Vec<Worksh
 
         let is_column_divider = buffer.iter().all(|str| str.ends_with(" "));
         if is_column_divider || i >= total_row_length - 1 {
-            let operand = match operand_iterator.next() {
-                Some("+") => Operand::Addition,
-                Some("*") => Operand::Multiply,
-                Some(value) => panic!("Failed reading operand '{}'", value),
-                None => panic!("Expected an operand, but found none"),
-            };
+            let operand = operand_iterator.next().ok_or_else(|| {
+                AocError::InvalidInput(format!(
+                    "worksheet column {} has no operand",
+                    worksheets.len() + 1
+                ))
+            })?;
 
             let mut worksheet = WorksheetColumn {
                 number_texts: Vec::new(),
@@ -118,10 +138,10 @@ fn parse_worklist_columns(number_rows: &[&str], operand_row: &str) -> Vec<Worksh
         }
     }
 
-    worksheets
+    Ok(worksheets)
 }
 
-enum Operand {
+pub enum Operand {
     Addition,
     Multiply,
 }
@@ -135,24 +155,20 @@ impl Operand {
     }
 }
 
-struct WorksheetColumn {
-    number_texts: Vec<String>,
-    operand: Operand,
+pub struct WorksheetColumn {
+    pub number_texts: Vec<String>,
+    pub operand: Operand,
 }
 
 impl WorksheetColumn {
-    fn calculate_horizontal(&self) -> u64 {
+    pub fn calculate_horizontal(&self) -> Result<u64, AocError> {
         let numbers = self
             .number_texts
             .iter()
-            .map(|number_text| number_text.trim())
-            .map(|number_text| {
-                u64::from_str(number_text)
-                    .unwrap_or_else(|e| panic!("Failed to parse {}: {}", number_text, e))
-            })
-            .collect();
-
-        self.operand.apply(numbers)
+            .map(|number_text| parse_number(number_text))
+            .collect::<Result<_, _>>()?;
+
+        Ok(self.operand.apply(numbers))
     }
 
     // 123 328  51 64
@@ -164,7 +180,7 @@ impl WorksheetColumn {
     // 175 * 581 * 32
     // 8 + 248 + 369
     // 356 * 24 * 1
-    fn calculate_vertical(&self) -> u64 {
+    pub fn calculate_vertical(&self) -> Result<u64, AocError> {
         let mut pivoted_texts = vec!["".to_owned(); self.number_texts[0].len()];
         for text in &self.number_texts {
             for (index, char) in text.chars().rev().enumerate() {
@@ -176,35 +192,37 @@ impl WorksheetColumn {
 
         let numbers = pivoted_texts
             .iter()
-            .map(|number_text| number_text.trim())
-            .map(|number_text| {
-                u64::from_str(number_text)
-                    .unwrap_or_else(|e| panic!("Failed to parse {}: {}", number_text, e))
-            })
-            .collect();
-
-        self.operand.apply(numbers)
+            .map(|number_text| parse_number(number_text))
+            .collect::<Result<_, _>>()?;
+
+        Ok(self.operand.apply(numbers))
     }
 }
 
+fn parse_number(number_text: &str) -> Result<u64, AocError> {
+    u64::from_str(number_text.trim()).map_err(|_| {
+        AocError::InvalidInput(format!("worksheet column has no number in '{number_text}'"))
+    })
+}
+
 #[test]
 fn test_run_example_part_1() {
-    let example_data = read_to_string("./puzzle-inputs/day-6-example.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-6-example.txt", err
-        )
-    });
-    assert_eq!(run_part_1(example_data.as_str()), 4277556);
+    let example_data = InputDirectory::default()
+        .read(6, InputKind::Example(Part::One))
+        .unwrap();
+    assert_eq!(
+        Day6.part_1(&Day6.parse(&example_data).unwrap()).unwrap(),
+        4277556
+    );
 }
 
 #[test]
 fn test_run_example_part_2() {
-    let example_data = read_to_string("./puzzle-inputs/day-6-example.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-6-example.txt", err
-        )
-    });
-    assert_eq!(run_part_2(example_data.as_str()), 3263827);
+    let example_data = InputDirectory::default()
+        .read(6, InputKind::Example(Part::Two))
+        .unwrap();
+    assert_eq!(
+        Day6.part_2(&Day6.parse(&example_data).unwrap()).unwrap(),
+        3263827
+    );
 }
@@ -1,118 +1,150 @@
-use crate::stopwatch::time;
+use crate::error::AocError;
+use crate::export::{self, Picture};
+#[cfg(test)]
+use crate::input::{InputDirectory, InputKind};
+use crate::log::{info, trace};
+use crate::puzzle_input::PuzzleInput;
+#[cfg(test)]
+use crate::solution::Part;
+use crate::solution::Solution;
+use crate::visualize::{self, Color, Frame};
 use std::collections::HashMap;
-use std::fs::read_to_string;
-
-pub fn run() {
-    let example_data = read_to_string("./puzzle-inputs/day-7-example.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-7-example.txt", err
-        )
-    });
-    let puzzle_data = read_to_string("./puzzle-inputs/day-7-input.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-7-input.txt", err
-        )
-    });
 
-    time("Day 7, Part 1 Example", || {
-        run_part_1(example_data.as_str());
-    });
-    time("Day 7, Part 1 Puzzle", || {
-        run_part_1(puzzle_data.as_str());
-    });
+pub struct Day7;
 
-    time("Day 7, Part 2 Example", || {
-        run_part_2(example_data.as_str());
-    });
-    time("Day 7, Part 2 Puzzle", || {
-        run_part_2(puzzle_data.as_str());
-    });
-}
+impl Solution for Day7 {
+    const DAY: u8 = 7;
 
-fn run_part_1(input: &str) -> u32 {
-    let diagram = parse_manifold_diagram(input);
+    type Input = TachyonManifoldDiagram;
+    type Answer1 = u32;
+    type Answer2 = u64;
 
-    let split_count = shoot_beam(diagram);
+    fn parse(&self, input: &PuzzleInput) -> Result<TachyonManifoldDiagram, AocError> {
+        parse_manifold_diagram(input)
+    }
 
-    println!("The tachyon beam splits {split_count} times.");
+    fn part_1(&self, diagram: &TachyonManifoldDiagram) -> Result<u32, AocError> {
+        let split_count = shoot_beam(diagram);
 
-    split_count
-}
+        info!("The tachyon beam splits {split_count} times.");
 
-fn run_part_2(input: &str) -> u64 {
-    let diagram = parse_manifold_diagram(input);
+        Ok(split_count)
+    }
 
-    let paths = simulate_tachyon_particles(diagram);
+    fn part_2(&self, diagram: &TachyonManifoldDiagram) -> Result<u64, AocError> {
+        let paths = simulate_tachyon_particles(diagram)?;
 
-    println!("There are {paths} paths that a tachyon particle can take.");
+        info!("There are {paths} paths that a tachyon particle can take.");
 
-    paths
+        Ok(paths)
+    }
 }
 
-fn shoot_beam(diagram: TachyonManifoldDiagram) -> u32 {
-    let mut beam_origins: Vec<Coordinate> = Vec::new();
-    beam_origins.push(diagram.beam_origin);
-
+/// Moves every beam down one row at a time, so a frame can be drawn for every row. A beam that
+/// reaches a row another beam already lights up joins it, so every splitter is counted once.
+pub fn shoot_beam(diagram: &TachyonManifoldDiagram) -> u32 {
+    let height = diagram.splitters.iter().map(|c| c.y + 2).max().unwrap_or(1);
+
+    // Beams that are still moving down, and the beams that ended on a splitter.
+    let mut fronts: Vec<VerticalBeam> = vec![VerticalBeam {
+        x: diagram.beam_origin.x,
+        y_min: diagram.beam_origin.y,
+        y_max: diagram.beam_origin.y,
+    }];
     let mut vertical_beams: Vec<VerticalBeam> = Vec::new(); // Performance potential: Swap Vec with HashMap that buckets beams by X coordinate.
 
-    while !beam_origins.is_empty() {
-        let mut origins_for_iteration: Vec<Coordinate> = Vec::new();
-        beam_origins.iter().for_each(|origin| {
-            origins_for_iteration.push(origin.clone());
-        });
-        beam_origins.clear();
+    for row in diagram.beam_origin.y + 1..height {
+        let mut next_fronts: Vec<VerticalBeam> = Vec::new();
 
-        for origin in origins_for_iteration {
-            if vertical_beams.iter().any(|beam| beam.intersects(origin)) {
-                continue; // We already have seen this beam, so ignore it.
-            }
+        for (index, mut front) in fronts.iter().copied().enumerate() {
+            front.y_max = row;
 
-            let splitter = diagram
-                .splitters
-                .iter()
-                .filter(|splitter| splitter.x == origin.x)
-                .find(|splitter| splitter.y > origin.y);
-
-            match splitter {
-                None => {}
-                Some(splitter) => {
-                    vertical_beams.push(VerticalBeam {
-                        x: origin.x,
-                        y_min: origin.y,
-                        y_max: splitter.y,
-                    });
-
-                    let split_left = Coordinate {
-                        x: splitter.x - 1,
-                        y: splitter.y,
-                    };
-                    let split_right = Coordinate {
-                        x: splitter.x + 1,
-                        y: splitter.y,
-                    };
-
-                    beam_origins.push(split_left);
-                    beam_origins.push(split_right);
+            let splitter = Coordinate { x: front.x, y: row };
+            if !diagram.splitters.contains(&splitter) {
+                next_fronts.push(front);
+                continue;
+            }
+            vertical_beams.push(front);
+
+            for x in [splitter.x - 1, splitter.x + 1] {
+                let origin = Coordinate { x, y: row };
+                let lit = vertical_beams.iter().any(|beam| beam.intersects(origin))
+                    || next_fronts
+                        .iter()
+                        .chain(&fronts[index + 1..])
+                        .any(|beam| beam.x == x);
+                if lit {
+                    continue; // We already have seen this beam, so ignore it.
                 }
+
+                next_fronts.push(VerticalBeam {
+                    x,
+                    y_min: row,
+                    y_max: row,
+                });
             }
         }
+
+        fronts = next_fronts;
+        visualize::emit(|| beam_frame(diagram, &vertical_beams, &fronts, row, height));
     }
 
-    vertical_beams
-        .iter()
-        .map(|b| Coordinate { x: b.x, y: b.y_max })
-        .collect::<Vec<_>>()
-        .len() as u32
+    export::emit("beams", || {
+        Picture::Grid(beam_frame(
+            diagram,
+            &vertical_beams,
+            &fronts,
+            height - 1,
+            height,
+        ))
+    });
+
+    vertical_beams.len() as u32
+}
+
+/// The diagram with the beams as far as they got by `row`.
+fn beam_frame(
+    diagram: &TachyonManifoldDiagram,
+    beams: &[VerticalBeam],
+    fronts: &[VerticalBeam],
+    row: i32,
+    height: i32,
+) -> Frame {
+    let width = diagram.splitters.iter().map(|c| c.x + 2).max().unwrap_or(1);
+    let mut frame = Frame::new(
+        format!("Row {row} of {}", height - 1),
+        width.max(diagram.beam_origin.x + 1) as usize,
+        height as usize,
+    );
+
+    for beam in beams.iter().chain(fronts) {
+        for y in beam.y_min..=beam.y_max {
+            frame.set(beam.x as usize, y as usize, '|', Color::Cyan);
+        }
+    }
+    for splitter in &diagram.splitters {
+        let hit = beams
+            .iter()
+            .any(|beam| beam.y_max == splitter.y && beam.x == splitter.x);
+        let color = if hit { Color::Yellow } else { Color::Gray };
+        frame.set(splitter.x as usize, splitter.y as usize, '^', color);
+    }
+    frame.set(
+        diagram.beam_origin.x as usize,
+        diagram.beam_origin.y as usize,
+        'S',
+        Color::Green,
+    );
+
+    frame
 }
 
-fn simulate_tachyon_particles(diagram: TachyonManifoldDiagram) -> u64 {
+pub fn simulate_tachyon_particles(diagram: &TachyonManifoldDiagram) -> Result<u64, AocError> {
     let mut splitter_data: HashMap<Coordinate, Splitter> = diagram
         .splitters
         .iter()
         .map(|coordinate| Splitter {
-            coordinate: coordinate.clone(),
+            coordinate: *coordinate,
             amount_of_paths_to: 0,
             amount_of_paths_from: 0,
         })
@@ -128,7 +160,7 @@ fn simulate_tachyon_particles(diagram: TachyonManifoldDiagram) -> u64 {
             .fold(HashMap::new(), |mut map, coordinate| {
                 let coordinates_at_x = map.entry(coordinate.x).or_insert(Vec::new());
                 coordinates_at_x.push(*coordinate);
-                coordinates_at_x.sort_by(|c1, c2| c1.x.cmp(&c2.x));
+                coordinates_at_x.sort_by_key(|c1| c1.x);
                 map
             });
 
@@ -139,34 +171,28 @@ fn simulate_tachyon_particles(diagram: TachyonManifoldDiagram) -> u64 {
             .fold(HashMap::new(), |mut map, coordinate| {
                 let coordinates_at_y = map.entry(coordinate.y).or_insert(Vec::new());
                 coordinates_at_y.push(*coordinate);
-                coordinates_at_y.sort_by(|c1, c2| c1.y.cmp(&c2.y));
+                coordinates_at_y.sort_by_key(|c1| c1.y);
                 map
             });
 
     // Init first splitter
     let first_splitter = splitters_at_x
         .get(&diagram.beam_origin.x)
-        .unwrap_or_else(|| panic!("Could not find splitters for X{}", diagram.beam_origin.x))
-        .first()
-        .unwrap_or_else(|| {
-            panic!(
-                "Could not find first splitter for X{}",
+        .and_then(|splitters| splitters.first())
+        .ok_or_else(|| {
+            AocError::Unsolvable(format!(
+                "the beam at x {} never hits a splitter",
                 diagram.beam_origin.x
-            )
-        });
+            ))
+        })?;
     let mut first_splitter = splitter_data
-        .get_mut(&first_splitter)
+        .get_mut(first_splitter)
         .unwrap_or_else(|| panic!("Could not find splitter data for {:?}", first_splitter))
         .clone();
     first_splitter.amount_of_paths_to += 1;
     splitter_data.insert(first_splitter.coordinate, first_splitter);
 
-    let max_y = diagram
-        .splitters
-        .iter()
-        .map(|c| c.y)
-        .max()
-        .expect("Could not find any splitter to find the max Y");
+    let max_y = diagram.splitters.iter().map(|c| c.y).max().unwrap_or(0);
     for y in 0..max_y {
         let Some(splitters) = splitters_at_y.get(&y) else {
             continue;
@@ -174,17 +200,17 @@ fn simulate_tachyon_particles(diagram: TachyonManifoldDiagram) -> u64 {
 
         for splitter in splitters {
             let mut splitter = splitter_data.get_mut(splitter).unwrap().clone();
-            println!("Hit splitter @{:?}", splitter);
+            trace!("Hit splitter @{:?}", splitter);
 
             let target_left = splitter.coordinate.x - 1;
             let next_left = splitters_at_x
                 .entry(target_left)
-                .or_insert(Vec::new())
+                .or_default()
                 .iter()
                 .filter(|splitter| splitter.x == target_left)
                 .find(|coordinate| coordinate.y > splitter.coordinate.y);
             if let Some(coordinate) = next_left {
-                println!("Next left: {:?}", coordinate);
+                trace!("Next left: {:?}", coordinate);
 
                 splitter.amount_of_paths_from += 1;
                 let mut splitter_at_left = splitter_data.get(coordinate).unwrap().clone();
@@ -195,12 +221,12 @@ fn simulate_tachyon_particles(diagram: TachyonManifoldDiagram) -> u64 {
             let target_right = splitter.coordinate.x + 1;
             let next_right = splitters_at_x
                 .entry(target_right)
-                .or_insert(Vec::new())
+                .or_default()
                 .iter()
                 .filter(|splitter| splitter.x == target_right)
                 .find(|coordinate| coordinate.y > splitter.coordinate.y);
             if let Some(coordinate) = next_right {
-                println!("Next right: {:?}", coordinate);
+                trace!("Next right: {:?}", coordinate);
 
                 splitter.amount_of_paths_from += 1;
                 let mut splitter_at_right = splitter_data.get(coordinate).unwrap().clone();
@@ -215,24 +241,25 @@ fn simulate_tachyon_particles(diagram: TachyonManifoldDiagram) -> u64 {
     let direct_endpoints: u64 = splitter_data
         .values()
         .filter(|splitter| splitter.amount_of_paths_from == 0)
-        .map(|splitter| (splitter.amount_of_paths_to * 2) as u64)
+        .map(|splitter| splitter.amount_of_paths_to * 2)
         .sum();
 
     let indirect_endpoints: u64 = splitter_data
         .values()
         .filter(|splitter| splitter.amount_of_paths_from == 1)
-        .map(|splitter| splitter.amount_of_paths_to as u64)
+        .map(|splitter| splitter.amount_of_paths_to)
         .sum();
 
-    direct_endpoints + indirect_endpoints
+    Ok(direct_endpoints + indirect_endpoints)
 }
 
 #[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
-struct Coordinate {
-    x: i32,
-    y: i32,
+pub struct Coordinate {
+    pub x: i32,
+    pub y: i32,
 }
 
+#[derive(Clone, Copy)]
 struct VerticalBeam {
     x: i32,
     y_min: i32,
@@ -256,178 +283,215 @@ impl VerticalBeam {
     }
 }
 
-struct TachyonManifoldDiagram {
-    beam_origin: Coordinate,
-    splitters: Vec<Coordinate>,
+pub struct TachyonManifoldDiagram {
+    pub beam_origin: Coordinate,
+    pub splitters: Vec<Coordinate>,
 }
 
-fn parse_manifold_diagram(input: &str) -> TachyonManifoldDiagram {
-    let input = input.replace('\r', "");
-    let lines = input.split('\n');
-
+pub fn parse_manifold_diagram(input: &PuzzleInput) -> Result<TachyonManifoldDiagram, AocError> {
     let mut beam_origin: Option<Coordinate> = None;
     let mut splitters: Vec<Coordinate> = Vec::new();
-    for (line_index, line_text) in lines.enumerate() {
-        for (character_index, character) in line_text.chars().enumerate() {
-            match character {
-                'S' => {
-                    beam_origin = Some(Coordinate {
-                        x: character_index as i32,
-                        y: line_index as i32,
-                    })
-                }
-                '^' => {
-                    let position = Coordinate {
-                        x: character_index as i32,
-                        y: line_index as i32,
-                    };
-                    splitters.push(position);
-                }
-                '.' => {}
-                other => panic!(
-                    "Parsed unexpected character '{other}' at line {line_index}, char {character_index}"
-                ),
+    for (character_index, line_index, character) in input.grid().cells() {
+        match character {
+            'S' => {
+                beam_origin = Some(Coordinate {
+                    x: character_index as i32,
+                    y: line_index as i32,
+                })
+            }
+            '^' => {
+                let position = Coordinate {
+                    x: character_index as i32,
+                    y: line_index as i32,
+                };
+                splitters.push(position);
+            }
+            '.' => {}
+            other => {
+                return Err(input.cell_error(
+                    character_index,
+                    line_index,
+                    format!("unexpected character '{other}'"),
+                ));
             }
         }
     }
 
-    let beam_origin = beam_origin.expect("Did not find any beam origin in the input");
+    let beam_origin = beam_origin.ok_or_else(|| {
+        AocError::InvalidInput("did not find any beam origin 'S' in the diagram".to_owned())
+    })?;
 
-    TachyonManifoldDiagram {
+    Ok(TachyonManifoldDiagram {
         beam_origin,
         splitters,
-    }
+    })
 }
 
 #[test]
 fn test_run_example_part_1() {
-    let path = "./puzzle-inputs/day-7-example.txt";
-    let example_data =
-        read_to_string(path).unwrap_or_else(|err| panic!("Failed to read file {path}: {err}"));
-    assert_eq!(run_part_1(example_data.as_str()), 21);
+    let example_data = InputDirectory::default()
+        .read(7, InputKind::Example(Part::One))
+        .unwrap();
+    assert_eq!(
+        Day7.part_1(&Day7.parse(&example_data).unwrap()).unwrap(),
+        21
+    );
 }
 
 #[test]
 fn test_run_example_part_2() {
-    let path = "./puzzle-inputs/day-7-example.txt";
-    let example_data =
-        read_to_string(path).unwrap_or_else(|err| panic!("Failed to read file {path}: {err}"));
-    assert_eq!(run_part_2(example_data.as_str()), 40);
+    let example_data = InputDirectory::default()
+        .read(7, InputKind::Example(Part::Two))
+        .unwrap();
+    assert_eq!(
+        Day7.part_2(&Day7.parse(&example_data).unwrap()).unwrap(),
+        40
+    );
 }
 
 #[test]
 fn test_shoot_beam_with_one_splitter() {
-    let mut splitters = Vec::new();
-    splitters.push(Coordinate { x: 0, y: 1 });
+    let splitters = vec![Coordinate { x: 0, y: 1 }];
 
     let diagram = TachyonManifoldDiagram {
         beam_origin: Coordinate { x: 0, y: 0 },
         splitters,
     };
 
-    let splits = shoot_beam(diagram);
+    let splits = shoot_beam(&diagram);
 
     assert_eq!(splits, 1);
 }
 
 #[test]
 fn test_shoot_beam_with_two_splitters() {
-    let mut splitters = Vec::new();
-    splitters.push(Coordinate { x: 0, y: 1 });
-    splitters.push(Coordinate { x: 1, y: 3 });
+    let splitters = vec![Coordinate { x: 0, y: 1 }, Coordinate { x: 1, y: 3 }];
 
     let diagram = TachyonManifoldDiagram {
         beam_origin: Coordinate { x: 0, y: 0 },
         splitters,
     };
 
-    let splits = shoot_beam(diagram);
+    let splits = shoot_beam(&diagram);
 
     assert_eq!(splits, 2);
 }
 
 #[test]
 fn test_shoot_beam_with_two_splitters_that_share_a_target() {
-    let mut splitters = Vec::new();
-    splitters.push(Coordinate { x: 1, y: 1 }); // First hit
-    splitters.push(Coordinate { x: 0, y: 3 });
-    splitters.push(Coordinate { x: 2, y: 3 });
-    splitters.push(Coordinate { x: 10, y: 3 }); // Should not hit
+    let splitters = vec![
+        Coordinate { x: 1, y: 1 }, // First hit
+        Coordinate { x: 0, y: 3 },
+        Coordinate { x: 2, y: 3 },
+        Coordinate { x: 10, y: 3 }, // Should not hit
+    ];
 
     let diagram = TachyonManifoldDiagram {
         beam_origin: Coordinate { x: 1, y: 0 },
         splitters,
     };
 
-    let splits = shoot_beam(diagram);
+    let splits = shoot_beam(&diagram);
 
     assert_eq!(splits, 3);
 }
 
 #[test]
 fn test_simulate_particle_with_two_splitters() {
-    let mut splitters = Vec::new();
-    //..S..
-    //..^..
-    //...^.
-    splitters.push(Coordinate { x: 0, y: 1 });
-    splitters.push(Coordinate { x: 1, y: 3 });
+    let splitters = vec![
+        //..S..
+        //..^..
+        //...^.
+        Coordinate { x: 0, y: 1 },
+        Coordinate { x: 1, y: 3 },
+    ];
 
     let diagram = TachyonManifoldDiagram {
         beam_origin: Coordinate { x: 0, y: 0 },
         splitters,
     };
 
-    let splits = simulate_tachyon_particles(diagram);
+    let splits = simulate_tachyon_particles(&diagram).unwrap();
 
     assert_eq!(splits, 3);
 }
 
 #[test]
 fn test_simulate_particle_that_hits_the_same_splitter_twice() {
-    let mut splitters = Vec::new();
-    //..S..
-    //..^..
-    //.^.^.
-    //..^..
-    //.....
-    splitters.push(Coordinate { x: 1, y: 1 });
-    splitters.push(Coordinate { x: 0, y: 2 });
-    splitters.push(Coordinate { x: 2, y: 2 });
-
-    splitters.push(Coordinate { x: 1, y: 3 }); // Target Splitter
+    let splitters = vec![
+        //..S..
+        //..^..
+        //.^.^.
+        //..^..
+        //.....
+        Coordinate { x: 1, y: 1 },
+        Coordinate { x: 0, y: 2 },
+        Coordinate { x: 2, y: 2 },
+        Coordinate { x: 1, y: 3 }, // Target Splitter
+    ];
 
     let diagram = TachyonManifoldDiagram {
         beam_origin: Coordinate { x: 1, y: 0 },
         splitters,
     };
 
-    let hits = simulate_tachyon_particles(diagram);
+    let hits = simulate_tachyon_particles(&diagram).unwrap();
 
     assert_eq!(hits, 6);
 }
 
 #[test]
 fn test_simulate_tachyon_particle() {
-    let mut splitters = Vec::new();
-    //..S..
-    //..^..
-    //.^.^.
-    //..^..
-    //...^.
-    splitters.push(Coordinate { x: 1, y: 1 });
-    splitters.push(Coordinate { x: 0, y: 2 });
-    splitters.push(Coordinate { x: 2, y: 2 });
-    splitters.push(Coordinate { x: 1, y: 3 });
-
-    splitters.push(Coordinate { x: 2, y: 4 }); // Target Splitter
+    let splitters = vec![
+        //..S..
+        //..^..
+        //.^.^.
+        //..^..
+        //...^.
+        Coordinate { x: 1, y: 1 },
+        Coordinate { x: 0, y: 2 },
+        Coordinate { x: 2, y: 2 },
+        Coordinate { x: 1, y: 3 },
+        Coordinate { x: 2, y: 4 }, // Target Splitter
+    ];
 
     let diagram = TachyonManifoldDiagram {
         beam_origin: Coordinate { x: 1, y: 0 },
         splitters,
     };
 
-    let hits = simulate_tachyon_particles(diagram);
+    let hits = simulate_tachyon_particles(&diagram).unwrap();
 
     assert_eq!(hits, 8);
 }
+
+#[test]
+fn test_frames_follow_the_beams() {
+    let diagram = TachyonManifoldDiagram {
+        beam_origin: Coordinate { x: 0, y: 0 },
+        splitters: vec![Coordinate { x: 0, y: 1 }, Coordinate { x: 1, y: 3 }],
+    };
+
+    let (_, frames) = visualize::record(|| shoot_beam(&diagram));
+
+    let titles: Vec<&str> = frames.iter().map(|frame| frame.title.as_str()).collect();
+    assert_eq!(
+        titles,
+        ["Row 1 of 4", "Row 2 of 4", "Row 3 of 4", "Row 4 of 4"]
+    );
+    assert_eq!(frames[0].cell(1, 1).symbol, '|');
+    assert_eq!(frames[0].cell(1, 2).symbol, '.');
+    assert_eq!(frames[1].cell(1, 2).symbol, '|');
+    assert_eq!(frames[1].cell(1, 3).color, Color::Gray);
+    assert_eq!(frames[2].cell(1, 3).color, Color::Yellow);
+    assert_eq!(frames[2].cell(2, 4).symbol, '.');
+    assert_eq!(frames[3].cell(2, 4).symbol, '|');
+}
+
+#[test]
+fn test_shoot_beam_counts_a_splitter_reached_twice_once() {
+    let example = "..S..\n.....\n..^..\n.....\n...^.\n.....\n.^...\n.....\n..^..\n.....\n";
+    let diagram = Day7.parse(&PuzzleInput::new(example)).unwrap();
+
+    assert_eq!(shoot_beam(&diagram), 4);
+}
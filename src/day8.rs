@@ -1,71 +1,89 @@
-use crate::stopwatch::time;
+use crate::error::{AocError, ParseError};
+#[cfg(test)]
+use crate::input::{InputDirectory, InputKind};
+use crate::log::{info, trace};
+use crate::puzzle_input::PuzzleInput;
+#[cfg(test)]
+use crate::solution::Part;
+use crate::solution::Solution;
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
-use std::fs::read_to_string;
 use std::hash::Hash;
-use std::rc::Rc;
-
-pub fn run() {
-    let example_data = read_to_string("./puzzle-inputs/day-8-example.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-8-example.txt", err
-        )
-    });
-    let puzzle_data = read_to_string("./puzzle-inputs/day-8-input.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-8-input.txt", err
-        )
-    });
+use std::sync::Arc;
+
+/// The example asks for the 10 closest connections, the real puzzle for 1000 of them.
+const EXAMPLE_CONNECTIONS: u32 = 10;
+const PUZZLE_CONNECTIONS: u32 = 1000;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Input = Playground;
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &PuzzleInput) -> Result<Playground, AocError> {
+        Ok(Playground {
+            junction_boxes: input.parse_lines(parse_junction_box)?,
+            connections_to_make: if input.is_example() {
+                EXAMPLE_CONNECTIONS
+            } else {
+                PUZZLE_CONNECTIONS
+            },
+        })
+    }
 
-    time("Day 8, Part 1 Example", || {
-        run_part_1(example_data.as_str(), 10);
-    });
-    time("Day 8, Part 1 Puzzle", || {
-        run_part_1(puzzle_data.as_str(), 1000);
-    });
+    fn part_1(&self, playground: &Playground) -> Result<u32, AocError> {
+        Ok(connect_closest_junction_boxes(
+            &playground.junction_boxes,
+            playground.connections_to_make,
+        ))
+    }
 
-    time("Day 8, Part 2 Example", || {
-        run_part_2(example_data.as_str());
-    });
-    time("Day 8, Part 2 Puzzle", || {
-        run_part_2(puzzle_data.as_str());
-    });
+    fn part_2(&self, playground: &Playground) -> Result<u64, AocError> {
+        connect_all_junction_boxes(&playground.junction_boxes)
+    }
 }
 
-fn run_part_1(input: &str, connections_to_make: u32) -> u32 {
-    let junction_boxes = parse_junction_boxes(input);
+pub struct Playground {
+    pub junction_boxes: Vec<JunctionBox>,
+    /// How many of the closest pairs part 1 connects, which the examples ask fewer of.
+    pub connections_to_make: u32,
+}
 
-    let distances: Vec<JunctionBoxDistance> = find_distances_between_points(&junction_boxes);
+pub fn connect_closest_junction_boxes(
+    junction_boxes: &[JunctionBox],
+    connections_to_make: u32,
+) -> u32 {
+    let distances: Vec<JunctionBoxDistance> = find_distances_between_points(junction_boxes);
 
-    let mut circuits: HashSet<Rc<Circuit>> = HashSet::new();
-    let mut circuit_lookup: HashMap<JunctionBox, Rc<Circuit>> = HashMap::new();
+    let mut circuits: HashSet<Arc<Circuit>> = HashSet::new();
+    let mut circuit_lookup: HashMap<JunctionBox, Arc<Circuit>> = HashMap::new();
     junction_boxes.iter().for_each(|point| {
         let circuit = Circuit {
             identity: point.clone(),
             junction_boxes: HashSet::from([point.clone()]),
         };
-        let circuit = Rc::new(circuit);
+        let circuit = Arc::new(circuit);
         circuit_lookup.insert(point.clone(), circuit.clone());
         circuits.insert(circuit.clone());
     });
 
-    let mut count = 0;
-    for distance in &distances {
-        if count > connections_to_make {
+    for (count, distance) in distances.iter().enumerate() {
+        if count >= connections_to_make as usize {
             break;
         }
 
-        println!("[{count}]: Processing distance {:?}", distance);
+        trace!("[{count}]: Processing distance {:?}", distance);
 
-        let one = circuit_lookup.get(&distance.first_box.clone().into()).unwrap_or_else(|| panic!("Distance {distance:?} has invalid first_box, circuit lookup does not have an entry for point {:?}", distance.first_box));
-        let other = circuit_lookup.get(&distance.second_box.clone().into()).unwrap_or_else(|| panic!("Distance {distance:?} has invalid second_box, circuit lookup does not have an entry for point {:?}", distance.second_box));
+        let one = circuit_lookup.get(&distance.first_box.clone()).unwrap_or_else(|| panic!("Distance {distance:?} has invalid first_box, circuit lookup does not have an entry for point {:?}", distance.first_box));
+        let other = circuit_lookup.get(&distance.second_box.clone()).unwrap_or_else(|| panic!("Distance {distance:?} has invalid second_box, circuit lookup does not have an entry for point {:?}", distance.second_box));
 
         let is_same_circuit = one == other;
-        count += 1;
         if is_same_circuit {
-            println!(
+            trace!(
                 "Skipping distance {distance:?} because {one:?} and {other:?} are part of the same circuit"
             );
             continue;
@@ -79,40 +97,39 @@ fn run_part_1(input: &str, connections_to_make: u32) -> u32 {
         );
     }
 
-    let circuits: Vec<&Rc<Circuit>> = circuits.iter().collect();
+    let circuits: Vec<&Arc<Circuit>> = circuits.iter().collect();
 
     let mut circuits: Vec<usize> = circuits
         .iter()
-        .map(|circuit| circuit.junction_boxes.len().clone())
+        .map(|circuit| circuit.junction_boxes.len())
         .collect();
     circuits.sort_by(|a, b| b.cmp(a));
-    let size: usize = circuits[..=2].iter().product();
+    // All boxes can end up in fewer than three circuits when many connections are made.
+    let size: usize = circuits.iter().take(3).product();
 
-    println!("The product of the largest 10 circuits is {size}");
+    info!("The product of the largest 10 circuits is {size}");
 
     size as u32
 }
 
-fn run_part_2(input: &str) -> u64 {
-    let junction_boxes = parse_junction_boxes(input);
-
-    let distances: Vec<JunctionBoxDistance> = find_distances_between_points(&junction_boxes);
+pub fn connect_all_junction_boxes(junction_boxes: &[JunctionBox]) -> Result<u64, AocError> {
+    let distances: Vec<JunctionBoxDistance> = find_distances_between_points(junction_boxes);
 
-    let mut circuits: HashSet<Rc<Circuit>> = HashSet::new();
-    let mut circuit_lookup: HashMap<JunctionBox, Rc<Circuit>> = HashMap::new();
+    let mut circuits: HashSet<Arc<Circuit>> = HashSet::new();
+    let mut circuit_lookup: HashMap<JunctionBox, Arc<Circuit>> = HashMap::new();
     junction_boxes.iter().for_each(|point| {
         let circuit = Circuit {
             identity: point.clone(),
             junction_boxes: HashSet::from([point.clone()]),
         };
-        let circuit = Rc::new(circuit);
+        let circuit = Arc::new(circuit);
         circuit_lookup.insert(point.clone(), circuit.clone());
         circuits.insert(circuit.clone());
     });
 
     for distance in &distances {
-        let one = circuit_lookup.get(&distance.first_box.clone().into()).unwrap_or_else(|| panic!("Distance {distance:?} has invalid first_box, circuit lookup does not have an entry for point {:?}", distance.first_box));
-        let other = circuit_lookup.get(&distance.second_box.clone().into()).unwrap_or_else(|| panic!("Distance {distance:?} has invalid second_box, circuit lookup does not have an entry for point {:?}", distance.second_box));
+        let one = circuit_lookup.get(&distance.first_box.clone()).unwrap_or_else(|| panic!("Distance {distance:?} has invalid first_box, circuit lookup does not have an entry for point {:?}", distance.first_box));
+        let other = circuit_lookup.get(&distance.second_box.clone()).unwrap_or_else(|| panic!("Distance {distance:?} has invalid second_box, circuit lookup does not have an entry for point {:?}", distance.second_box));
 
         let is_same_circuit = one == other;
         if is_same_circuit {
@@ -121,10 +138,10 @@ fn run_part_2(input: &str) -> u64 {
 
         if circuits.len() == 2 {
             let result = distance.first_box.x * distance.second_box.x;
-            println!(
+            info!(
                 "The product of X coordinates of the two last two circuits to be connected is {result:?}"
             );
-            return result;
+            return Ok(result);
         }
 
         merge_circuits(
@@ -135,12 +152,15 @@ fn run_part_2(input: &str) -> u64 {
         );
     }
 
-    panic!("Did not connect last circuits!");
+    Err(AocError::Unsolvable(format!(
+        "{} junction boxes never form a single circuit",
+        junction_boxes.len()
+    )))
 }
 
 fn merge_circuits(
-    circuits: &mut HashSet<Rc<Circuit>>,
-    circuit_lookup: &mut HashMap<JunctionBox, Rc<Circuit>>,
+    circuits: &mut HashSet<Arc<Circuit>>,
+    circuit_lookup: &mut HashMap<JunctionBox, Arc<Circuit>>,
     first_point: JunctionBox,
     second_point: JunctionBox,
 ) {
@@ -165,7 +185,7 @@ fn merge_circuits(
             .cloned()
             .collect(),
     };
-    let joined_circuit_ref = Rc::new(joined_circuit);
+    let joined_circuit_ref = Arc::new(joined_circuit);
 
     circuits.remove(first_circuit);
     circuits.remove(second_circuit);
@@ -177,7 +197,7 @@ fn merge_circuits(
     }
 }
 
-fn find_distances_between_points(junction_boxes: &Vec<JunctionBox>) -> Vec<JunctionBoxDistance> {
+fn find_distances_between_points(junction_boxes: &[JunctionBox]) -> Vec<JunctionBoxDistance> {
     let mut distances: Vec<JunctionBoxDistance> = Vec::new();
 
     for (index, first_box) in junction_boxes.iter().enumerate() {
@@ -187,22 +207,22 @@ fn find_distances_between_points(junction_boxes: &Vec<JunctionBox>) -> Vec<Junct
             }
 
             distances.push(JunctionBoxDistance {
-                distance: first_box.distance_sq(&second_box) as u64,
+                distance: first_box.distance_sq(second_box),
                 first_box: first_box.clone(),
                 second_box: second_box.clone(),
             });
         }
     }
 
-    distances.sort_by(|a, b| a.distance.cmp(&b.distance));
+    distances.sort_by_key(|a| a.distance);
     distances
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-struct JunctionBox {
-    x: u64,
-    y: u64,
-    z: u64,
+pub struct JunctionBox {
+    pub x: u64,
+    pub y: u64,
+    pub z: u64,
 }
 
 impl JunctionBox {
@@ -222,7 +242,7 @@ struct JunctionBoxDistance {
 
 impl PartialOrd for JunctionBoxDistance {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.distance.partial_cmp(&other.distance)
+        Some(self.cmp(other))
     }
 }
 
@@ -244,57 +264,77 @@ impl Hash for Circuit {
     }
 }
 
-fn parse_junction_boxes(input: &str) -> Vec<JunctionBox> {
-    let input = input.replace('\r', "");
-
-    let mut boxes: Vec<JunctionBox> = Vec::new();
-    for box_text in input.lines() {
-        let coords: Vec<f64> = box_text
-            .split(',')
-            .map(|coord| coord.parse::<f64>().unwrap())
-            .collect();
-        let junction_box = JunctionBox {
-            x: coords[0] as u64,
-            y: coords[1] as u64,
-            z: coords[2] as u64,
-        };
-        boxes.push(junction_box);
+pub fn parse_junction_box(box_text: &str) -> Result<JunctionBox, ParseError> {
+    let mut coords: Vec<u64> = Vec::new();
+
+    let mut offset = 0;
+    for coord in box_text.split(',') {
+        let value = coord
+            .parse::<u64>()
+            .map_err(|_| ParseError::new(offset, format!("invalid coordinate '{coord}'")))?;
+        coords.push(value);
+
+        offset += coord.len() + 1;
     }
 
-    boxes
+    let [x, y, z] = coords[..] else {
+        return Err(ParseError::new(
+            0,
+            format!(
+                "expected 3 coordinates like '162,817,812', found {}",
+                coords.len()
+            ),
+        ));
+    };
+
+    Ok(JunctionBox { x, y, z })
 }
 
 #[test]
 fn test_run_example_part_1() {
-    let path = "./puzzle-inputs/day-8-example.txt";
-    let example_data =
-        read_to_string(path).unwrap_or_else(|err| panic!("Failed to read file {path}: {err}"));
-    assert_eq!(run_part_1(example_data.as_str(), 9), 40);
+    let example_data = InputDirectory::default()
+        .read(8, InputKind::Example(Part::One))
+        .unwrap();
+    let playground = Day8.parse(&example_data).unwrap();
+    assert_eq!(playground.connections_to_make, EXAMPLE_CONNECTIONS);
+    assert_eq!(Day8.part_1(&playground).unwrap(), 40);
+}
+
+#[test]
+fn test_connections_do_not_depend_on_the_input_size() {
+    let playground = Day8
+        .parse(&PuzzleInput::new("162,817,812\n57,618,57\n"))
+        .unwrap();
+
+    assert_eq!(playground.connections_to_make, PUZZLE_CONNECTIONS);
 }
 
 #[test]
 fn test_run_example_part_2() {
-    let path = "./puzzle-inputs/day-8-example.txt";
-    let example_data =
-        read_to_string(path).unwrap_or_else(|err| panic!("Failed to read file {path}: {err}"));
-    assert_eq!(run_part_2(example_data.as_str()), 25272);
+    let example_data = InputDirectory::default()
+        .read(8, InputKind::Example(Part::Two))
+        .unwrap();
+    assert_eq!(
+        Day8.part_2(&Day8.parse(&example_data).unwrap()).unwrap(),
+        25272
+    );
 }
 
 #[test]
 fn test_rc_multiple_maps() {
-    let mut circuit_junction_box_lut: Vec<Rc<Circuit>> = Vec::new();
-    let mut junction_box_circuit_lut: HashMap<JunctionBox, Rc<Circuit>> = HashMap::new();
+    let mut circuit_junction_box_lut: Vec<Arc<Circuit>> = Vec::new();
+    let mut junction_box_circuit_lut: HashMap<JunctionBox, Arc<Circuit>> = HashMap::new();
 
     let junction_box = JunctionBox { x: 0, y: 0, z: 0 };
 
-    let hashed_point: JunctionBox = junction_box.into();
+    let hashed_point: JunctionBox = junction_box;
 
     let circuit = Circuit {
         identity: hashed_point.clone(),
         junction_boxes: HashSet::from([hashed_point.clone()]),
     };
 
-    let circuit_ref = Rc::new(circuit);
+    let circuit_ref = Arc::new(circuit);
     circuit_junction_box_lut.push(circuit_ref.clone());
     junction_box_circuit_lut.insert(hashed_point.clone(), circuit_ref.clone());
 
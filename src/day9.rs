@@ -1,63 +1,58 @@
-use crate::stopwatch::time;
-use std::fs::read_to_string;
-
-pub fn run() {
-    let example_data = read_to_string("./puzzle-inputs/day-9-example.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-9-example.txt", err
-        )
-    });
-    let puzzle_data = read_to_string("./puzzle-inputs/day-9-input.txt").unwrap_or_else(|err| {
-        panic!(
-            "Failed to read file {}: {}",
-            "./puzzle-inputs/day-9-input.txt", err
-        )
-    });
-
-    time("Day 9, Part 1 Example", || {
-        run_part_1(example_data.as_str());
-    });
-    time("Day 9, Part 1 Puzzle", || {
-        run_part_1(puzzle_data.as_str());
-    });
-
-    time("Day 9, Part 2 Example", || {
-        run_part_2(example_data.as_str());
-    });
-    time("Day 9, Part 2 Puzzle", || {
-        run_part_2(puzzle_data.as_str());
-    });
-}
-
-fn run_part_1(input: &str) -> u64 {
-    let coordinates = parse_coordinates(input);
+use crate::error::{AocError, ParseError};
+use crate::export::{self, Drawing, Picture, Shape};
+#[cfg(test)]
+use crate::input::{InputDirectory, InputKind};
+use crate::log::info;
+use crate::puzzle_input::PuzzleInput;
+#[cfg(test)]
+use crate::solution::Part;
+use crate::solution::Solution;
+use crate::visualize::Color;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<Coordinate>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &PuzzleInput) -> Result<Vec<Coordinate>, AocError> {
+        input.parse_lines(parse_coordinate)
+    }
 
-    let largest_rectangle = find_largest_rectangle(&coordinates);
+    fn part_1(&self, coordinates: &Vec<Coordinate>) -> Result<u64, AocError> {
+        let largest_rectangle = find_largest_rectangle(coordinates)
+            .ok_or_else(|| AocError::Unsolvable("there are no red tiles".to_owned()))?;
 
-    let size = largest_rectangle.0.calculate_area(&largest_rectangle.1);
+        let size = largest_rectangle.0.calculate_area(&largest_rectangle.1);
+        export::emit("largest-rectangle", || {
+            Picture::Drawing(rectangle_drawing(coordinates, largest_rectangle))
+        });
 
-    println!("Largest rectangle has an area of {size}");
+        info!("Largest rectangle has an area of {size}");
 
-    size
-}
+        Ok(size)
+    }
 
-fn run_part_2(_input: &str) -> u64 {
-    // Create the perimeter of the grid
+    fn part_2(&self, _coordinates: &Vec<Coordinate>) -> Result<u64, AocError> {
+        // Create the perimeter of the grid
 
-    // loop through all rectangles
+        // loop through all rectangles
 
-    // Create function to test if a coordinate is within a rectangle
+        // Create function to test if a coordinate is within a rectangle
 
-    // Filter by any rectangle that is entirely within the perimeter
+        // Filter by any rectangle that is entirely within the perimeter
 
-    0
+        Ok(0)
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
-struct Coordinate {
-    x: u64,
-    y: u64,
+pub struct Coordinate {
+    pub x: u64,
+    pub y: u64,
 }
 
 impl Coordinate {
@@ -66,59 +61,93 @@ impl Coordinate {
     }
 }
 
-fn parse_coordinates(input: &str) -> Vec<Coordinate> {
-    input.lines().map(|line| parse_coordinate(line)).collect()
+pub fn parse_coordinate(input: &str) -> Result<Coordinate, ParseError> {
+    let Some((x_text, y_text)) = input.split_once(',') else {
+        return Err(ParseError::new(
+            0,
+            format!("coordinate '{input}' is not of the form 'x,y'"),
+        ));
+    };
+
+    let parse_component = |text: &str, offset: usize| {
+        text.parse::<u64>()
+            .map_err(|_| ParseError::new(offset, format!("invalid coordinate component '{text}'")))
+    };
+
+    Ok(Coordinate {
+        x: parse_component(x_text, 0)?,
+        y: parse_component(y_text, x_text.len() + 1)?,
+    })
 }
 
-fn parse_coordinate(input: &str) -> Coordinate {
-    let mut split = input.split(',');
-    Coordinate {
-        x: split
-            .next()
-            .unwrap_or_else(|| panic!("Coordinate {input} was missing X component"))
-            .parse::<u64>()
-            .unwrap(),
-        y: split
-            .next()
-            .unwrap_or_else(|| panic!("Coordinate {input} was missing Y component"))
-            .parse::<u64>()
-            .unwrap(),
-    }
+/// The red tiles connected in order, and the rectangle over the tiles it covers.
+fn rectangle_drawing(
+    coordinates: &[Coordinate],
+    (first, second): (Coordinate, Coordinate),
+) -> Drawing {
+    let center = |coordinate: &Coordinate| (coordinate.x as f64 + 0.5, coordinate.y as f64 + 0.5);
+
+    let mut drawing = Drawing::new(format!(
+        "Largest rectangle between {},{} and {},{}",
+        first.x, first.y, second.x, second.y
+    ));
+    drawing.add(
+        Shape::Polygon(coordinates.iter().map(center).collect()),
+        Color::Red,
+    );
+    drawing.add(
+        Shape::Rectangle(
+            (first.x.min(second.x) as f64, first.y.min(second.y) as f64),
+            (
+                first.x.max(second.x) as f64 + 1.0,
+                first.y.max(second.y) as f64 + 1.0,
+            ),
+        ),
+        Color::Green,
+    );
+
+    drawing
 }
 
-fn find_largest_rectangle(coordinates: &[Coordinate]) -> (Coordinate, Coordinate) {
+pub fn find_largest_rectangle(coordinates: &[Coordinate]) -> Option<(Coordinate, Coordinate)> {
     let mut largest_area: Option<(Coordinate, Coordinate)> = None;
     for (index, first) in coordinates.iter().enumerate() {
         for second in coordinates[index..].iter() {
             let area = first.calculate_area(second);
-            if largest_area.is_none() {
-                largest_area = Some((first.clone(), second.clone()));
-            } else if area
-                > largest_area
-                    .unwrap()
-                    .0
-                    .calculate_area(&largest_area.unwrap().1)
+            if largest_area.is_none()
+                || area
+                    > largest_area
+                        .unwrap()
+                        .0
+                        .calculate_area(&largest_area.unwrap().1)
             {
-                largest_area = Some((first.clone(), second.clone()));
+                largest_area = Some((*first, *second));
             }
         }
     }
 
-    largest_area.unwrap_or_else(|| panic!("No squares found"))
+    largest_area
 }
 
 #[test]
 fn test_run_example_part_1() {
-    let path = "./puzzle-inputs/day-9-example.txt";
-    let example_data =
-        read_to_string(path).unwrap_or_else(|err| panic!("Failed to read file {path}: {err}"));
-    assert_eq!(run_part_1(example_data.as_str()), 50);
+    let example_data = InputDirectory::default()
+        .read(9, InputKind::Example(Part::One))
+        .unwrap();
+    assert_eq!(
+        Day9.part_1(&Day9.parse(&example_data).unwrap()).unwrap(),
+        50
+    );
 }
 
 #[test]
+#[ignore = "part 2 has not been solved yet"]
 fn test_run_example_part_2() {
-    let path = "./puzzle-inputs/day-9-example.txt";
-    let example_data =
-        read_to_string(path).unwrap_or_else(|err| panic!("Failed to read file {path}: {err}"));
-    assert_eq!(run_part_2(example_data.as_str()), 25272);
+    let example_data = InputDirectory::default()
+        .read(9, InputKind::Example(Part::Two))
+        .unwrap();
+    assert_eq!(
+        Day9.part_2(&Day9.parse(&example_data).unwrap()).unwrap(),
+        24
+    );
 }
@@ -0,0 +1,123 @@
+use crate::solution::Phase;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::PathBuf;
+
+/// Everything that can go wrong between reading an input file and printing an answer.
+#[derive(Debug)]
+pub enum AocError {
+    MissingInput(PathBuf),
+    Io(PathBuf, io::Error),
+    /// A line of the input that could not be parsed. Line and column start at 1.
+    Parse {
+        line: usize,
+        column: usize,
+        text: String,
+        message: String,
+    },
+    /// The input is missing something as a whole, like a section or a starting point.
+    InvalidInput(String),
+    /// The input was understood, but the puzzle has no answer for it.
+    Unsolvable(String),
+    /// The part ran past its deadline and gave up, see [crate::cancel].
+    Cancelled,
+    /// A failure while running one phase of a day, along with the file it was running against.
+    Failed {
+        day: u8,
+        phase: Phase,
+        path: PathBuf,
+        error: Box<AocError>,
+    },
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::MissingInput(path) => write!(
+                f,
+                "Input file {} does not exist, set {} or pass --input-dir to read inputs from \
+                 another directory",
+                path.display(),
+                crate::input::INPUT_DIR_VARIABLE
+            ),
+            AocError::Io(path, err) => write!(f, "Failed to read {}: {err}", path.display()),
+            AocError::Parse {
+                line,
+                column,
+                text,
+                message,
+            } => {
+                let gutter = " ".repeat(line.to_string().len());
+                write!(
+                    f,
+                    "line {line}, column {column}: {message}\n {gutter} |\n {line} | {text}\n \
+                     {gutter} | {}^",
+                    " ".repeat(column - 1)
+                )
+            }
+            AocError::InvalidInput(message) => write!(f, "Invalid input: {message}"),
+            AocError::Unsolvable(message) => write!(f, "No solution: {message}"),
+            AocError::Cancelled => f.write_str("Cancelled after running past the timeout"),
+            AocError::Failed {
+                day,
+                phase,
+                path,
+                error,
+            } => write!(f, "Day {day} {phase} of {}: {error}", path.display()),
+        }
+    }
+}
+
+/// A problem with a single line of input. Becomes an [AocError::Parse] once the line it was found on
+/// is known.
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    /// Byte offset into the line where the problem starts.
+    pub offset: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(offset: usize, message: impl Into<String>) -> ParseError {
+        ParseError {
+            offset,
+            message: message.into(),
+        }
+    }
+
+    /// Moves the error `offset` bytes to the right, for errors found in a slice of a longer line.
+    pub fn shifted(self, offset: usize) -> ParseError {
+        ParseError::new(self.offset + offset, self.message)
+    }
+
+    pub fn at_line(self, line: usize, text: &str) -> AocError {
+        let column = text
+            .get(..self.offset)
+            .map_or(self.offset, |before| before.chars().count())
+            + 1;
+
+        AocError::Parse {
+            line,
+            column,
+            text: text.to_owned(),
+            message: self.message,
+        }
+    }
+}
+
+#[test]
+fn test_parse_error_points_at_column() {
+    let error = ParseError::new(2, "invalid count 'x'").at_line(12, "L1x");
+
+    assert_eq!(
+        error.to_string(),
+        "line 12, column 3: invalid count 'x'\n    |\n 12 | L1x\n    |   ^"
+    );
+}
+
+#[test]
+fn test_parse_error_counts_characters() {
+    let error = ParseError::new(3, "unexpected").at_line(1, "éx!");
+
+    assert!(matches!(error, AocError::Parse { column: 3, .. }));
+}
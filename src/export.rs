@@ -0,0 +1,324 @@
+use crate::scoped::{self, Slot};
+use crate::visualize::{Color, EMPTY, Frame};
+use std::cell::RefCell;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// What empty cells and the space around drawings are filled with.
+const BACKGROUND: (u8, u8, u8) = (32, 32, 32);
+
+/// A picture of a puzzle state that a solution can export.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Picture {
+    /// Written as both PPM and SVG, with a square per cell.
+    Grid(Frame),
+    /// Written as SVG only, as its coordinates can be far too large for a pixel per unit.
+    Drawing(Drawing),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// A closed outline through the points.
+    Polygon(Vec<(f64, f64)>),
+    /// A filled rectangle between two opposite corners.
+    Rectangle((f64, f64), (f64, f64)),
+}
+
+/// Shapes in the coordinates of a puzzle, scaled to fit the image when written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Drawing {
+    pub title: String,
+    shapes: Vec<(Shape, Color)>,
+}
+
+impl Drawing {
+    pub fn new(title: impl Into<String>) -> Drawing {
+        Drawing {
+            title: title.into(),
+            shapes: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, shape: Shape, color: Color) {
+        self.shapes.push((shape, color));
+    }
+
+    pub fn to_svg(&self) -> String {
+        let points: Vec<(f64, f64)> = self
+            .shapes
+            .iter()
+            .flat_map(|(shape, _)| match shape {
+                Shape::Polygon(points) => points.clone(),
+                Shape::Rectangle(from, to) => vec![*from, *to],
+            })
+            .collect();
+        let min_x = points.iter().map(|p| p.0).fold(f64::INFINITY, f64::min);
+        let min_y = points.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+        let max_x = points.iter().map(|p| p.0).fold(f64::NEG_INFINITY, f64::max);
+        let max_y = points.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max);
+        let (min_x, min_y, width, height) = if points.is_empty() {
+            (0.0, 0.0, 1.0, 1.0)
+        } else {
+            (
+                min_x,
+                min_y,
+                (max_x - min_x).max(1.0),
+                (max_y - min_y).max(1.0),
+            )
+        };
+        let margin = width.max(height) * 0.02;
+
+        let mut svg = svg_header(
+            &self.title,
+            (min_x - margin, min_y - margin),
+            (width + 2.0 * margin, height + 2.0 * margin),
+            800.0 * (width / width.max(height)).max(0.1),
+            800.0 * (height / width.max(height)).max(0.1),
+        );
+        for (shape, color) in &self.shapes {
+            let color = hex(color.rgb());
+            let _ = match shape {
+                Shape::Polygon(points) => writeln!(
+                    svg,
+                    r#"<polygon points="{}" fill="none" stroke="{color}" stroke-width="2" vector-effect="non-scaling-stroke"/>"#,
+                    points
+                        .iter()
+                        .map(|(x, y)| format!("{x},{y}"))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                ),
+                Shape::Rectangle(from, to) => writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{color}" fill-opacity="0.4" stroke="{color}" stroke-width="2" vector-effect="non-scaling-stroke"/>"#,
+                    from.0.min(to.0),
+                    from.1.min(to.1),
+                    (to.0 - from.0).abs(),
+                    (to.1 - from.1).abs()
+                ),
+            };
+        }
+        svg += "</svg>\n";
+
+        svg
+    }
+}
+
+fn svg_header(
+    title: &str,
+    origin: (f64, f64),
+    size: (f64, f64),
+    width: f64,
+    height: f64,
+) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.0}\" height=\"{height:.0}\" \
+         viewBox=\"{} {} {} {}\">\n<title>{}</title>\n\
+         <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+        origin.0,
+        origin.1,
+        size.0,
+        size.1,
+        escape(title),
+        origin.0,
+        origin.1,
+        size.0,
+        size.1,
+        hex(BACKGROUND)
+    )
+}
+
+fn hex((red, green, blue): (u8, u8, u8)) -> String {
+    format!("#{red:02x}{green:02x}{blue:02x}")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn cell_rgb(frame: &Frame, x: usize, y: usize) -> (u8, u8, u8) {
+    match frame.cell(x, y) {
+        cell if cell == EMPTY => BACKGROUND,
+        cell => cell.color.rgb(),
+    }
+}
+
+/// A binary PPM image with a square of `scale` by `scale` pixels per cell.
+pub fn grid_to_ppm(frame: &Frame, scale: usize) -> Vec<u8> {
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    let mut image = format!("P6\n{width} {height}\n255\n").into_bytes();
+
+    for y in 0..height {
+        for x in 0..width {
+            let (red, green, blue) = cell_rgb(frame, x / scale, y / scale);
+            image.extend([red, green, blue]);
+        }
+    }
+
+    image
+}
+
+/// An SVG image with a unit square per cell that is not empty.
+pub fn grid_to_svg(frame: &Frame, scale: usize) -> String {
+    let (width, height) = (frame.width() as f64, frame.height() as f64);
+    let mut svg = svg_header(
+        &frame.title,
+        (0.0, 0.0),
+        (width, height),
+        width * scale as f64,
+        height * scale as f64,
+    );
+
+    for y in 0..frame.height() {
+        for x in 0..frame.width() {
+            if frame.cell(x, y) != EMPTY {
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{x}" y="{y}" width="1" height="1" fill="{}"/>"#,
+                    hex(cell_rgb(frame, x, y))
+                );
+            }
+        }
+    }
+    svg += "</svg>\n";
+
+    svg
+}
+
+thread_local! {
+    static RECORDING: Slot<Vec<(String, Picture)>> = const { RefCell::new(None) };
+}
+
+/// Whether pictures emitted on this thread are kept.
+pub fn recording() -> bool {
+    scoped::is_set(&RECORDING)
+}
+
+/// Adds a picture, named for use in its file name, to the [record] this thread is in. The picture is
+/// only drawn while recording.
+pub fn emit(name: &str, picture: impl FnOnce() -> Picture) {
+    if recording() {
+        let picture = picture();
+        scoped::get(&RECORDING, |pictures| {
+            pictures.push((name.to_owned(), picture))
+        });
+    }
+}
+
+/// Runs `function` and returns the pictures it emitted on this thread next to its result.
+pub fn record<T>(function: impl FnOnce() -> T) -> (T, Vec<(String, Picture)>) {
+    let (result, pictures) = scoped::with(&RECORDING, Some(Vec::new()), function);
+
+    (result, pictures.unwrap_or_default())
+}
+
+/// Writes every picture into the directory as `<prefix>-<name>.ppm` and `.svg`, and returns the
+/// files it wrote.
+pub fn write_pictures(
+    pictures: &[(String, Picture)],
+    directory: &Path,
+    prefix: &str,
+    scale: usize,
+) -> Result<Vec<PathBuf>, String> {
+    fs::create_dir_all(directory)
+        .map_err(|err| format!("Failed to create {}: {err}", directory.display()))?;
+
+    let mut written = Vec::new();
+    for (name, picture) in pictures {
+        let path = directory.join(file_name(&format!("{prefix}-{name}")));
+        let files: Vec<(PathBuf, Vec<u8>)> = match picture {
+            Picture::Grid(frame) => vec![
+                (path.with_extension("ppm"), grid_to_ppm(frame, scale)),
+                (
+                    path.with_extension("svg"),
+                    grid_to_svg(frame, scale).into_bytes(),
+                ),
+            ],
+            Picture::Drawing(drawing) => {
+                vec![(path.with_extension("svg"), drawing.to_svg().into_bytes())]
+            }
+        };
+
+        for (path, contents) in files {
+            fs::write(&path, contents)
+                .map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+            written.push(path);
+        }
+    }
+
+    Ok(written)
+}
+
+/// Keeps letters and digits, so input names that are paths make a usable file name.
+fn file_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+
+    name.split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[test]
+fn test_grid_to_ppm() {
+    let mut frame = Frame::new("rolls", 2, 1);
+    frame.set(1, 0, '@', Color::Rgb(1, 2, 3));
+
+    let image = grid_to_ppm(&frame, 2);
+
+    let mut expected = b"P6\n4 2\n255\n".to_vec();
+    for _ in 0..2 {
+        expected.extend([32, 32, 32, 32, 32, 32, 1, 2, 3, 1, 2, 3]);
+    }
+    assert_eq!(image, expected);
+}
+
+#[test]
+fn test_grid_to_svg() {
+    let mut frame = Frame::new("a < b", 2, 2);
+    frame.set(0, 1, '|', Color::Rgb(255, 0, 16));
+
+    let svg = grid_to_svg(&frame, 10);
+
+    assert!(svg.starts_with(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20" viewBox="0 0 2 2">"#
+    ));
+    assert!(svg.contains("<title>a &lt; b</title>"));
+    assert!(svg.contains(r##"<rect x="0" y="1" width="1" height="1" fill="#ff0010"/>"##));
+    assert_eq!(svg.matches("<rect").count(), 2);
+}
+
+#[test]
+fn test_drawing_to_svg() {
+    let mut drawing = Drawing::new("tiles");
+    drawing.add(
+        Shape::Polygon(vec![(0.0, 0.0), (100.0, 0.0), (100.0, 50.0)]),
+        Color::Red,
+    );
+    drawing.add(Shape::Rectangle((10.0, 40.0), (0.0, 0.0)), Color::Green);
+
+    let svg = drawing.to_svg();
+
+    assert!(svg.contains(r#"width="800" height="400" viewBox="-2 -2 104 54""#));
+    assert!(svg.contains(r#"<polygon points="0,0 100,0 100,50""#));
+    assert!(svg.contains(r##"<rect x="0" y="0" width="10" height="40" fill="#859900""##));
+}
+
+#[test]
+fn test_file_name() {
+    assert_eq!(
+        file_name("day-4-../inputs/Day 4.txt-by-round"),
+        "day-4-inputs-day-4-txt-by-round"
+    );
+}
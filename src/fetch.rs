@@ -0,0 +1,171 @@
+use crate::http::{Request, send};
+use crate::input::{InputDirectory, InputKind};
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const YEAR: u16 = 2025;
+pub const SESSION_VARIABLE: &str = "AOC_SESSION";
+pub const BASE_URL_VARIABLE: &str = "AOC_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// The session cookie of a logged in Advent of Code account, which the inputs are tied to.
+pub struct Session(pub(crate) String);
+
+impl Session {
+    /// Reads the token from the given file, then `AOC_SESSION`, and otherwise `~/.aoc-session`.
+    pub fn resolve(file: Option<&Path>) -> Result<Session, String> {
+        resolve_session(file, env::var_os(SESSION_VARIABLE), env::var_os("HOME"))
+    }
+
+    pub fn cookie(&self) -> String {
+        format!("session={}", self.0)
+    }
+}
+
+fn resolve_session(
+    file: Option<&Path>,
+    variable: Option<OsString>,
+    home: Option<OsString>,
+) -> Result<Session, String> {
+    let path = match (file, variable) {
+        (Some(path), _) => path.to_path_buf(),
+        (None, Some(token)) if !token.is_empty() => {
+            return Ok(Session(token.to_string_lossy().trim().to_owned()));
+        }
+        _ => match home {
+            Some(home) => Path::new(&home).join(".aoc-session"),
+            None => return Err(format!("No session token, set {SESSION_VARIABLE}")),
+        },
+    };
+
+    let token = fs::read_to_string(&path).map_err(|err| {
+        format!(
+            "No session token, set {SESSION_VARIABLE} or write it to {} ({err})",
+            path.display()
+        )
+    })?;
+
+    match token.trim() {
+        "" => Err(format!("The session file {} is empty", path.display())),
+        token => Ok(Session(token.to_owned())),
+    }
+}
+
+/// Uses the URL given on the command line, then `AOC_BASE_URL`, and otherwise the real site.
+pub fn base_url(flag: Option<&str>) -> String {
+    let url = match (flag, env::var(BASE_URL_VARIABLE)) {
+        (Some(url), _) => url.to_owned(),
+        (None, Ok(url)) if !url.is_empty() => url,
+        _ => DEFAULT_BASE_URL.to_owned(),
+    };
+
+    url.trim_end_matches('/').to_owned()
+}
+
+pub enum Fetched {
+    Downloaded(PathBuf),
+    /// The input was downloaded before and is never requested again.
+    Cached(PathBuf),
+}
+
+/// Downloads the puzzle input of a day into the input directory, unless it is already there. An empty
+/// file, like the one `new` creates, does not count as downloaded.
+pub fn fetch(
+    day: u8,
+    directory: &InputDirectory,
+    base_url: &str,
+    session: impl FnOnce() -> Result<Session, String>,
+) -> Result<Fetched, String> {
+    let path = directory.path(day, InputKind::Puzzle);
+    if fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let url = format!("{base_url}/{YEAR}/day/{day}/input");
+    let response = send(&Request::get(&url).header("Cookie", &session()?.cookie()))?;
+    match response.status {
+        200 if !response.body.is_empty() => {}
+        200 => return Err(format!("{url} returned an empty input")),
+        400 | 401 | 500 => {
+            return Err(format!(
+                "{url} refused the session token ({}), it may have expired",
+                response.status
+            ));
+        }
+        404 => return Err(format!("Day {day} is not unlocked yet")),
+        status => {
+            return Err(format!("{url} answered {status}: {}", response.body.trim()));
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+    }
+    // Written next to the input first, so an interrupted write never looks like a cached input.
+    let partial = path.with_extension("txt.part");
+    fs::write(&partial, &response.body)
+        .and_then(|_| fs::rename(&partial, &path))
+        .map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+
+    Ok(Fetched::Downloaded(path))
+}
+
+#[test]
+fn test_resolve_session() {
+    let file = env::temp_dir().join(format!("aoc-2025-session-{}", std::process::id()));
+    fs::write(&file, "from-file\n").unwrap();
+    let home = env::temp_dir().join(format!("aoc-2025-no-home-{}", std::process::id()));
+
+    let from_file = resolve_session(Some(&file), Some("from-env".into()), None);
+    let from_env = resolve_session(None, Some("from-env".into()), None);
+    let missing = resolve_session(None, None, Some(home.into()));
+    fs::remove_file(&file).unwrap();
+
+    assert_eq!(from_file.unwrap().cookie(), "session=from-file");
+    assert_eq!(from_env.unwrap().cookie(), "session=from-env");
+    assert!(missing.is_err());
+}
+
+#[test]
+fn test_fetch_downloads_once() {
+    let root = env::temp_dir().join(format!("aoc-2025-fetch-{}", std::process::id()));
+    let directory = InputDirectory::resolve(Some(&root));
+    let (url, server) = crate::http::serve(vec![
+        "HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\nL68\n".to_owned(),
+    ]);
+    let session = || Ok(Session("secret".to_owned()));
+
+    fs::create_dir_all(&root).unwrap();
+    fs::write(root.join("day-3-input.txt"), "").unwrap();
+
+    let first = fetch(3, &directory, &url, session);
+    let second = fetch(3, &directory, &url, || {
+        panic!("a cached input needs no session")
+    });
+    let requests = server.join().unwrap();
+    let contents = fs::read_to_string(root.join("day-3-input.txt"));
+    fs::remove_dir_all(&root).unwrap();
+
+    assert!(matches!(first, Ok(Fetched::Downloaded(_))));
+    assert!(matches!(second, Ok(Fetched::Cached(_))));
+    assert_eq!(contents.unwrap(), "L68\n");
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("GET /2025/day/3/input HTTP/1.1\r\n"));
+    assert!(requests[0].contains("Cookie: session=secret\r\n"));
+}
+
+#[test]
+fn test_fetch_reports_locked_days() {
+    let root = env::temp_dir().join(format!("aoc-2025-fetch-locked-{}", std::process::id()));
+    let directory = InputDirectory::resolve(Some(&root));
+    let (url, server) = crate::http::serve(vec!["HTTP/1.1 404 Not Found\r\n\r\n".to_owned()]);
+
+    let result = fetch(25, &directory, &url, || Ok(Session("secret".to_owned())));
+    server.join().unwrap();
+
+    assert!(result.is_err_and(|message| message.contains("not unlocked")));
+    assert!(!root.join("day-25-input.txt").exists());
+}
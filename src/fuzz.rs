@@ -0,0 +1,742 @@
+use crate::log;
+use crate::puzzle_input::PuzzleInput;
+use crate::solution::{Day, Part};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+
+/// A SplitMix64 generator, so that every run can be repeated from its seed.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `low` up to and including `high`.
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        low + self.next_u64() % (high - low + 1)
+    }
+
+    /// Whether an event with this chance, from 0 to 1, happens.
+    pub fn chance(&mut self, chance: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64) < chance
+    }
+}
+
+/// Random inputs for a day, and a brute-force solution to check its answers against.
+pub struct Target {
+    pub day: u8,
+    generate: fn(&mut Rng) -> String,
+    /// The answer to a part, or `None` for an input the day does not have to handle.
+    oracle: fn(&str, Part) -> Option<String>,
+    /// Smaller versions of an input, tried while shrinking one the day gets wrong.
+    shrink: fn(&str) -> Vec<String>,
+}
+
+/// Every day that can be fuzzed, in calendar order.
+pub static TARGETS: &[Target] = &[
+    Target {
+        day: 1,
+        generate: generate_turns,
+        oracle: dial_oracle,
+        shrink: shrink_turns,
+    },
+    Target {
+        day: 2,
+        generate: generate_id_ranges,
+        oracle: invalid_ids_oracle,
+        shrink: shrink_id_ranges,
+    },
+    Target {
+        day: 3,
+        generate: generate_banks,
+        oracle: joltage_oracle,
+        shrink: shrink_banks,
+    },
+    Target {
+        day: 4,
+        generate: generate_rolls,
+        oracle: rolls_oracle,
+        shrink: shrink_grid,
+    },
+    Target {
+        day: 5,
+        generate: generate_inventory,
+        oracle: fresh_ingredients_oracle,
+        shrink: shrink_inventory,
+    },
+    Target {
+        day: 7,
+        generate: generate_manifold,
+        oracle: tachyon_oracle,
+        shrink: shrink_manifold,
+    },
+];
+
+pub fn find(day: u8) -> Option<&'static Target> {
+    TARGETS.iter().find(|target| target.day == day)
+}
+
+/// An input where the answer of a day differs from the brute-force one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch {
+    pub part: Part,
+    pub input: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Part {} answered {} instead of {} for this input:\n{}",
+            self.part,
+            self.actual,
+            self.expected,
+            self.input.trim_end()
+        )
+    }
+}
+
+/// Checks the day against inputs generated from the seed, and returns the first one it gets wrong,
+/// shrunk to the smallest input it still gets wrong.
+pub fn fuzz(day: &dyn Day, target: &Target, seed: u64, iterations: u32) -> Option<Mismatch> {
+    let mut rng = Rng::new(seed);
+
+    for _ in 0..iterations {
+        let input = (target.generate)(&mut rng);
+        let mismatch = Part::ALL
+            .into_iter()
+            .find_map(|part| check(day, target, &input, part));
+        if let Some(mismatch) = mismatch {
+            return Some(shrink(day, target, mismatch));
+        }
+    }
+
+    None
+}
+
+fn shrink(day: &dyn Day, target: &Target, mut mismatch: Mismatch) -> Mismatch {
+    while let Some(smaller) = (target.shrink)(&mismatch.input)
+        .iter()
+        .find_map(|input| check(day, target, input, mismatch.part))
+    {
+        mismatch = smaller;
+    }
+
+    mismatch
+}
+
+fn check(day: &dyn Day, target: &Target, input: &str, part: Part) -> Option<Mismatch> {
+    let expected = (target.oracle)(input, part)?;
+    let actual = solve(day, input, part);
+
+    (actual != expected).then(|| Mismatch {
+        part,
+        input: input.to_owned(),
+        expected,
+        actual,
+    })
+}
+
+/// The answer of the day, or what went wrong instead. Panics are caught, as they are findings too.
+fn solve(day: &dyn Day, input: &str, part: Part) -> String {
+    let (result, _) = log::capture(|| {
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            let parsed = day.parse(&PuzzleInput::new(input))?;
+            day.solve(part, parsed.as_ref())
+        }))
+    });
+
+    match result {
+        Ok(Ok(answer)) => answer,
+        Ok(Err(err)) => format!("an error ({err})"),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            format!("a panic ({message})")
+        }
+    }
+}
+
+/// The items with each one left out in turn.
+fn without_each(items: &[&str], separator: &str) -> Vec<String> {
+    (0..items.len())
+        .map(|skip| {
+            let mut kept = items.to_vec();
+            kept.remove(skip);
+            kept.join(separator)
+        })
+        .collect()
+}
+
+/// The items with each one replaced by its smaller versions in turn.
+fn with_each_smaller(
+    items: &[&str],
+    separator: &str,
+    smaller: impl Fn(&str) -> Vec<String>,
+) -> Vec<String> {
+    let mut candidates = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        for replacement in smaller(item) {
+            let mut changed: Vec<&str> = items.to_vec();
+            changed[index] = &replacement;
+            candidates.push(changed.join(separator));
+        }
+    }
+
+    candidates
+}
+
+/// The two halves of a range of numbers written as `first-last`.
+fn halve_range(range: &str) -> Vec<String> {
+    let Some((first, last)) = range.split_once('-') else {
+        return Vec::new();
+    };
+    let (Ok(first), Ok(last)) = (first.parse::<u64>(), last.parse::<u64>()) else {
+        return Vec::new();
+    };
+    if first >= last {
+        return Vec::new();
+    }
+    let middle = first + (last - first) / 2;
+
+    vec![
+        format!("{first}-{middle}"),
+        format!("{}-{last}", middle + 1),
+    ]
+}
+
+fn generate_turns(rng: &mut Rng) -> String {
+    let mut turns = String::new();
+    for _ in 0..rng.between(1, 30) {
+        let direction = if rng.chance(0.5) { 'L' } else { 'R' };
+        let clicks = if rng.chance(0.2) {
+            rng.between(100, 999)
+        } else {
+            rng.between(1, 99)
+        };
+        turns += &format!("{direction}{clicks}\n");
+    }
+
+    turns
+}
+
+/// Turns the dial one click at a time.
+fn dial_oracle(input: &str, part: Part) -> Option<String> {
+    let mut position: i32 = 50;
+    let mut zeros = 0;
+
+    for line in input.lines() {
+        let (direction, clicks) = line.split_at_checked(1)?;
+        let step = match direction {
+            "L" => -1,
+            "R" => 1,
+            _ => return None,
+        };
+        let clicks: i32 = clicks.parse().ok().filter(|c| (1..=1000).contains(c))?;
+        for _ in 0..clicks {
+            position = (position + step).rem_euclid(100);
+            if part == Part::Two && position == 0 {
+                zeros += 1;
+            }
+        }
+        if part == Part::One && position == 0 {
+            zeros += 1;
+        }
+    }
+
+    Some(zeros.to_string())
+}
+
+fn shrink_turns(input: &str) -> Vec<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut candidates = without_each(&lines, "\n");
+    candidates.extend(with_each_smaller(&lines, "\n", |turn| {
+        let (direction, clicks) = turn.split_at(1);
+        match clicks.parse::<u32>() {
+            Ok(clicks) if clicks > 1 => vec![format!("{direction}{}", clicks / 2)],
+            _ => Vec::new(),
+        }
+    }));
+
+    candidates
+}
+
+fn generate_id_ranges(rng: &mut Rng) -> String {
+    let mut ranges = Vec::new();
+    for _ in 0..rng.between(1, 5) {
+        // Ranges around a number made of a repeated sequence find far more invalid ids.
+        let around = if rng.chance(0.5) {
+            let sequence = rng.between(1, 999).to_string();
+            let repeats = rng.between(2, (9 / sequence.len()) as u64) as usize;
+            sequence.repeat(repeats).parse().unwrap()
+        } else {
+            let digits = rng.between(1, 7) as u32;
+            rng.between(1, 10_u64.pow(digits))
+        };
+        let first = around.saturating_sub(rng.between(0, 100)).max(1);
+        let last = around + rng.between(0, 100);
+        ranges.push(format!("{first}-{last}"));
+    }
+
+    ranges.join(",")
+}
+
+/// Checks every id in the ranges by comparing its digits as text.
+fn invalid_ids_oracle(input: &str, part: Part) -> Option<String> {
+    let mut sum = 0_u64;
+
+    for range in input.lines().flat_map(|line| line.split(',')) {
+        let (first, last) = range.split_once('-')?;
+        let (first, last): (u64, u64) = (first.parse().ok()?, last.parse().ok()?);
+        if first == 0 || last < first || last - first > 10_000 {
+            return None;
+        }
+        sum += (first..=last)
+            .filter(|id| is_repeated_sequence(&id.to_string(), part))
+            .sum::<u64>();
+    }
+
+    Some(sum.to_string())
+}
+
+fn is_repeated_sequence(digits: &str, part: Part) -> bool {
+    let length = digits.len();
+
+    (1..length)
+        .filter(|size| length.is_multiple_of(*size))
+        .filter(|size| part == Part::Two || size * 2 == length)
+        .any(|size| digits == digits[..size].repeat(length / size))
+}
+
+fn shrink_id_ranges(input: &str) -> Vec<String> {
+    let ranges: Vec<&str> = input.trim_end().split(',').collect();
+    let mut candidates = without_each(&ranges, ",");
+    candidates.extend(with_each_smaller(&ranges, ",", halve_range));
+
+    candidates
+}
+
+fn generate_banks(rng: &mut Rng) -> String {
+    let mut banks = String::new();
+    for _ in 0..rng.between(1, 6) {
+        // Few different joltages make for many ties, where picking the first one matters.
+        let highest = rng.between(2, 9);
+        for _ in 0..rng.between(12, 16) {
+            banks += &rng.between(1, highest).to_string();
+        }
+        banks += "\n";
+    }
+
+    banks
+}
+
+/// Tries every combination of batteries in every bank.
+fn joltage_oracle(input: &str, part: Part) -> Option<String> {
+    let batteries = match part {
+        Part::One => 2,
+        Part::Two => 12,
+    };
+    let mut total = 0_u64;
+
+    for line in input.lines() {
+        let joltages: Vec<u64> = line
+            .chars()
+            .map(|c| c.to_digit(10).map(u64::from))
+            .collect::<Option<_>>()?;
+        if !(batteries..=16).contains(&joltages.len()) {
+            return None;
+        }
+        total += (0_u32..1 << joltages.len())
+            .filter(|picked| picked.count_ones() == batteries as u32)
+            .map(|picked| {
+                joltages
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| picked & 1 << index != 0)
+                    .fold(0, |joltage, (_, battery)| joltage * 10 + battery)
+            })
+            .max()?;
+    }
+
+    Some(total.to_string())
+}
+
+fn shrink_banks(input: &str) -> Vec<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut candidates = without_each(&lines, "\n");
+    candidates.extend(with_each_smaller(&lines, "\n", |bank| {
+        (0..bank.len())
+            .map(|skip| format!("{}{}", &bank[..skip], &bank[skip + 1..]))
+            .collect()
+    }));
+
+    candidates
+}
+
+fn generate_rolls(rng: &mut Rng) -> String {
+    let (width, height) = (rng.between(1, 12), rng.between(1, 12));
+    let density = rng.between(2, 8) as f64 / 10.0;
+    let mut grid = String::new();
+    for _ in 0..height {
+        for _ in 0..width {
+            grid.push(if rng.chance(density) { '@' } else { '.' });
+        }
+        grid.push('\n');
+    }
+
+    grid
+}
+
+/// Looks at all eight neighbours of every roll, and removes rolls a round at a time.
+fn rolls_oracle(input: &str, part: Part) -> Option<String> {
+    let mut rolls: BTreeSet<(i32, i32)> = BTreeSet::new();
+    for (y, line) in input.lines().enumerate() {
+        for (x, cell) in line.chars().enumerate() {
+            match cell {
+                '@' => {
+                    rolls.insert((x as i32, y as i32));
+                }
+                '.' => {}
+                _ => return None,
+            }
+        }
+    }
+
+    let mut removed = 0;
+    loop {
+        let removable: Vec<(i32, i32)> = rolls
+            .iter()
+            .filter(|(x, y)| {
+                let neighbours = (-1..=1)
+                    .flat_map(|dy| (-1..=1).map(move |dx| (x + dx, y + dy)))
+                    .filter(|neighbour| neighbour != &(*x, *y) && rolls.contains(neighbour))
+                    .count();
+                neighbours < 4
+            })
+            .copied()
+            .collect();
+        if part == Part::One {
+            return Some(removable.len().to_string());
+        }
+        if removable.is_empty() {
+            return Some(removed.to_string());
+        }
+        removed += removable.len();
+        for roll in removable {
+            rolls.remove(&roll);
+        }
+    }
+}
+
+/// The grid without each row, without each column, and with each symbol cleared in turn.
+fn shrink_grid(input: &str) -> Vec<String> {
+    let rows: Vec<&str> = input.lines().collect();
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let mut candidates = without_each(&rows, "\n");
+
+    for column in 0..width {
+        let without_column: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                row.chars()
+                    .enumerate()
+                    .filter(|(x, _)| *x != column)
+                    .map(|(_, cell)| cell)
+                    .collect()
+            })
+            .collect();
+        candidates.push(without_column.join("\n"));
+    }
+
+    candidates.extend(with_each_smaller(&rows, "\n", |row| {
+        row.char_indices()
+            .filter(|(_, cell)| *cell != '.')
+            .map(|(x, _)| format!("{}.{}", &row[..x], &row[x + 1..]))
+            .collect()
+    }));
+
+    candidates
+}
+
+fn generate_inventory(rng: &mut Rng) -> String {
+    let mut inventory = String::new();
+    for _ in 0..rng.between(1, 8) {
+        let first = rng.between(0, 60);
+        inventory += &format!("{first}-{}\n", first + rng.between(0, 20));
+    }
+    inventory += "\n";
+    for _ in 0..rng.between(1, 10) {
+        inventory += &format!("{}\n", rng.between(0, 90));
+    }
+
+    inventory
+}
+
+/// Counts fresh ingredients by listing every id in every range.
+fn fresh_ingredients_oracle(input: &str, part: Part) -> Option<String> {
+    let (ranges, ids) = input.split_once("\n\n")?;
+    let ranges: Vec<(u64, u64)> = ranges
+        .lines()
+        .map(|range| {
+            let (first, last) = range.split_once('-')?;
+            let (first, last) = (first.parse().ok()?, last.parse().ok()?);
+            (first <= last && last - first <= 10_000).then_some((first, last))
+        })
+        .collect::<Option<_>>()?;
+    let ids: Vec<u64> = ids
+        .lines()
+        .map(|id| id.parse().ok())
+        .collect::<Option<_>>()?;
+    if ranges.is_empty() || ids.is_empty() {
+        return None;
+    }
+
+    let fresh: BTreeSet<u64> = ranges
+        .iter()
+        .flat_map(|(first, last)| *first..=*last)
+        .collect();
+    let answer = match part {
+        Part::One => ids.iter().filter(|id| fresh.contains(id)).count(),
+        Part::Two => fresh.len(),
+    };
+
+    Some(answer.to_string())
+}
+
+fn shrink_inventory(input: &str) -> Vec<String> {
+    let Some((ranges, ids)) = input.split_once("\n\n") else {
+        return Vec::new();
+    };
+    let ranges: Vec<&str> = ranges.lines().collect();
+    let ids: Vec<&str> = ids.lines().collect();
+
+    let mut smaller_ranges = without_each(&ranges, "\n");
+    smaller_ranges.extend(with_each_smaller(&ranges, "\n", halve_range));
+    let mut candidates: Vec<String> = smaller_ranges
+        .into_iter()
+        .map(|ranges| format!("{ranges}\n\n{}\n", ids.join("\n")))
+        .collect();
+    candidates.extend(
+        without_each(&ids, "\n")
+            .into_iter()
+            .map(|ids| format!("{}\n\n{ids}\n", ranges.join("\n"))),
+    );
+
+    candidates
+}
+
+/// A diagram shaped like the puzzle ones, with a splitter below the start and the splitters of
+/// every next level between the places the beams of the level above end up.
+fn generate_manifold(rng: &mut Rng) -> String {
+    let levels = rng.between(1, 7) as usize;
+    let density = rng.between(4, 10) as f64 / 10.0;
+    let (width, start) = (2 * levels + 3, levels + 1);
+
+    let mut rows = vec![vec!['.'; width]; 2 * levels + 2];
+    rows[0][start] = 'S';
+    for level in 0..levels {
+        for offset in (0..=level).map(|step| start - level + 2 * step) {
+            if level == 0 || rng.chance(density) {
+                rows[2 * level + 2][offset] = '^';
+            }
+        }
+    }
+
+    rows.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+/// Takes out a level of splitters along with the empty row above it, so the diagram keeps the shape
+/// of the puzzle ones, or a column or a symbol the way [shrink_grid] does.
+fn shrink_manifold(input: &str) -> Vec<String> {
+    let rows: Vec<&str> = input.lines().collect();
+    let mut candidates: Vec<String> = (1..rows.len().saturating_sub(1))
+        .step_by(2)
+        .map(|first| [&rows[..first], &rows[first + 2..]].concat().join("\n"))
+        .collect();
+    candidates.extend(
+        shrink_grid(input)
+            .into_iter()
+            .filter(|candidate| candidate.lines().count() == rows.len()),
+    );
+
+    candidates
+}
+
+/// Follows the beams down one row at a time, counting the particles in every column. Like in the
+/// puzzle, rows with splitters need an empty row between them.
+fn tachyon_oracle(input: &str, part: Part) -> Option<String> {
+    let rows: Vec<Vec<char>> = input.lines().map(|row| row.chars().collect()).collect();
+    let width = rows.first()?.len();
+    let valid = rows.iter().all(|row| {
+        row.len() == width
+            && row.iter().all(|cell| ".^S".contains(*cell))
+            && row.first() != Some(&'^')
+            && row.last() != Some(&'^')
+            && !row.windows(2).any(|pair| pair == ['^', '^'])
+    });
+    let start = rows[0].iter().position(|cell| *cell == 'S')?;
+    let starts = rows.iter().flatten().filter(|cell| **cell == 'S').count();
+    let touching = rows
+        .windows(2)
+        .any(|pair| pair.iter().all(|row| row.contains(&'^')));
+    if !valid || touching || starts != 1 || rows.iter().all(|row| row[start] != '^') {
+        return None;
+    }
+
+    let mut particles: BTreeMap<usize, u64> = BTreeMap::from([(start, 1)]);
+    let mut splits = 0;
+    for row in &rows[1..] {
+        let mut next: BTreeMap<usize, u64> = BTreeMap::new();
+        for (x, count) in particles {
+            if row[x] == '^' {
+                splits += 1;
+                *next.entry(x - 1).or_default() += count;
+                *next.entry(x + 1).or_default() += count;
+            } else {
+                *next.entry(x).or_default() += count;
+            }
+        }
+        particles = next;
+    }
+
+    Some(match part {
+        Part::One => splits.to_string(),
+        Part::Two => particles.values().sum::<u64>().to_string(),
+    })
+}
+
+#[cfg(test)]
+use crate::registry;
+
+#[test]
+fn test_rng_repeats_from_its_seed() {
+    let numbers = |seed| {
+        let mut rng = Rng::new(seed);
+        (0..5).map(|_| rng.between(1, 6)).collect::<Vec<u64>>()
+    };
+
+    assert_eq!(numbers(7), numbers(7));
+    assert_ne!(numbers(7), numbers(8));
+    assert!(numbers(7).iter().all(|number| (1..=6).contains(number)));
+}
+
+#[test]
+fn test_oracles_answer_the_examples() {
+    let answers = |oracle: fn(&str, Part) -> Option<String>, input: &str| {
+        Part::ALL.map(|part| oracle(input, part).unwrap())
+    };
+
+    assert_eq!(
+        answers(
+            dial_oracle,
+            "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n"
+        ),
+        ["3", "6"]
+    );
+    assert_eq!(
+        answers(
+            joltage_oracle,
+            "987654321111111\n811111111111119\n234234234234278\n818181911112111\n"
+        ),
+        ["357", "3121910778619"]
+    );
+    assert_eq!(
+        answers(
+            fresh_ingredients_oracle,
+            "3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32\n"
+        ),
+        ["3", "14"]
+    );
+    assert_eq!(
+        answers(
+            tachyon_oracle,
+            ".......S.......\n...............\n.......^.......\n...............\n\
+             ......^.^......\n...............\n.....^.^.^.....\n...............\n\
+             ....^.^...^....\n...............\n...^.^...^.^...\n...............\n\
+             ..^...^.....^..\n...............\n.^.^.^.^.^...^.\n...............\n"
+        ),
+        ["21", "40"]
+    );
+    assert_eq!(
+        invalid_ids_oracle("11-22,95-115", Part::One),
+        Some("132".to_owned())
+    );
+    assert_eq!(
+        invalid_ids_oracle("95-115", Part::Two),
+        Some("210".to_owned())
+    );
+}
+
+#[test]
+fn test_generated_inputs_are_checked() {
+    let mut rng = Rng::new(2025);
+
+    for target in TARGETS {
+        for _ in 0..20 {
+            let input = (target.generate)(&mut rng);
+            for part in Part::ALL {
+                assert!(
+                    (target.oracle)(&input, part).is_some(),
+                    "day {} generated an input its oracle rejects:\n{input}",
+                    target.day
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_fuzz_shrinks_a_mismatch() {
+    // Answers one more fresh ingredient than there are, so that every input is a mismatch.
+    let target = Target {
+        oracle: |input, part| {
+            let answer: u64 = fresh_ingredients_oracle(input, part)?.parse().unwrap();
+            Some((answer + (part == Part::One) as u64).to_string())
+        },
+        ..*find(5).unwrap()
+    };
+
+    let mismatch = fuzz(registry::find(5).unwrap(), &target, 1, 10).unwrap();
+
+    assert_eq!(mismatch.part, Part::One);
+    assert_eq!(mismatch.input.lines().count(), 3);
+    assert_eq!(
+        mismatch.expected.parse::<u64>().unwrap(),
+        mismatch.actual.parse::<u64>().unwrap() + 1
+    );
+}
+
+#[test]
+fn test_manifolds_keep_their_shape_when_shrunk() {
+    let manifold = generate_manifold(&mut Rng::new(42));
+    let touching = |input: &str| {
+        let rows: Vec<&str> = input.lines().collect();
+        rows.windows(2)
+            .any(|pair| pair.iter().all(|row| row.contains('^')))
+    };
+
+    let candidates = shrink_manifold(&manifold);
+
+    assert!(!candidates.is_empty());
+    assert!(candidates.iter().all(|candidate| !touching(candidate)));
+    assert_eq!(tachyon_oracle("..S..\n..^..\n.^.^.\n", Part::One), None);
+}
@@ -0,0 +1,357 @@
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// Identifies this tool to the server, as Advent of Code asks automated tools to do.
+pub const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (Advent of Code runner written with only the standard library)"
+);
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: &'static str,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    /// Sent as `application/x-www-form-urlencoded`.
+    pub body: Option<String>,
+}
+
+impl Request {
+    pub fn get(url: &str) -> Request {
+        Request {
+            method: "GET",
+            url: url.to_owned(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    pub fn post(url: &str, body: String) -> Request {
+        Request {
+            method: "POST",
+            url: url.to_owned(),
+            headers: Vec::new(),
+            body: Some(body),
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Request {
+        self.headers.push((name.to_owned(), value.to_owned()));
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Sends the request with the [USER_AGENT]. The standard library has no TLS, so `https` URLs are
+/// handed to `curl`, while plain `http` is spoken directly, which is enough for local test servers.
+pub fn send(request: &Request) -> Result<Response, String> {
+    if request.url.starts_with("https://") {
+        send_with_curl(request)
+    } else if let Some(rest) = request.url.strip_prefix("http://") {
+        send_plain(request, rest)
+    } else {
+        Err(format!(
+            "Unsupported URL '{}', expected http:// or https://",
+            request.url
+        ))
+    }
+}
+
+fn send_plain(request: &Request, url_without_scheme: &str) -> Result<Response, String> {
+    let (authority, path) = match url_without_scheme.find('/') {
+        Some(index) => url_without_scheme.split_at(index),
+        None => (url_without_scheme, "/"),
+    };
+    let address = if authority.contains(':') {
+        authority.to_owned()
+    } else {
+        format!("{authority}:80")
+    };
+
+    let failed = |err: std::io::Error| format!("Request to {} failed: {err}", request.url);
+    let mut stream = TcpStream::connect(&address).map_err(failed)?;
+    stream.set_read_timeout(Some(TIMEOUT)).map_err(failed)?;
+
+    let mut message = format!(
+        "{} {path} HTTP/1.1\r\nHost: {authority}\r\nUser-Agent: {USER_AGENT}\r\nConnection: close\r\n",
+        request.method
+    );
+    for (name, value) in &request.headers {
+        message += &format!("{name}: {value}\r\n");
+    }
+    if let Some(body) = &request.body {
+        message += &format!(
+            "Content-Type: application/x-www-form-urlencoded\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+    } else {
+        message += "\r\n";
+    }
+    stream.write_all(message.as_bytes()).map_err(failed)?;
+
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw).map_err(failed)?;
+
+    parse_response(&raw).map_err(|err| format!("Invalid response from {}: {err}", request.url))
+}
+
+fn parse_response(raw: &[u8]) -> Result<Response, String> {
+    let head_end = raw
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or("the headers do not end")?;
+    let head = String::from_utf8_lossy(&raw[..head_end]);
+    let body = &raw[head_end + 4..];
+
+    let mut lines = head.lines();
+    let status_line = lines.next().unwrap_or_default();
+    let status = status_line
+        .split(' ')
+        .nth(1)
+        .and_then(|status| status.parse::<u16>().ok())
+        .ok_or_else(|| format!("invalid status line '{status_line}'"))?;
+
+    let header = |wanted: &str| {
+        head.lines().skip(1).find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.trim()
+                .eq_ignore_ascii_case(wanted)
+                .then(|| value.trim().to_owned())
+        })
+    };
+
+    // Content-Length counts bytes, so the body is cut before it is decoded.
+    let body = if header("Transfer-Encoding").is_some_and(|value| value.contains("chunked")) {
+        decode_chunked(body)?
+    } else if let Some(length) = header("Content-Length").and_then(|value| value.parse().ok()) {
+        body.get(..length).unwrap_or(body).to_vec()
+    } else {
+        body.to_vec()
+    };
+
+    Ok(Response {
+        status,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+fn decode_chunked(mut chunks: &[u8]) -> Result<Vec<u8>, String> {
+    let mut body = Vec::new();
+
+    loop {
+        let line_end = chunks
+            .windows(2)
+            .position(|window| window == b"\r\n")
+            .ok_or("a chunk size is missing")?;
+        let size = String::from_utf8_lossy(&chunks[..line_end]);
+        let rest = &chunks[line_end + 2..];
+        let size = usize::from_str_radix(size.split(';').next().unwrap_or_default().trim(), 16)
+            .map_err(|_| format!("invalid chunk size '{size}'"))?;
+        if size == 0 {
+            return Ok(body);
+        }
+
+        body.extend_from_slice(rest.get(..size).ok_or("a chunk is shorter than its size")?);
+        chunks = rest[size..].strip_prefix(b"\r\n").unwrap_or(&rest[size..]);
+    }
+}
+
+/// The headers and body are handed to curl as a config on its standard input rather than as
+/// arguments, so the session cookie does not show up in the process list.
+fn send_with_curl(request: &Request) -> Result<Response, String> {
+    let mut command = Command::new("curl");
+    command
+        .args(["--silent", "--show-error", "--request", request.method])
+        .args(["--max-time", &TIMEOUT.as_secs().to_string()])
+        .args(["--user-agent", USER_AGENT])
+        .args(["--write-out", "\n%{http_code}"])
+        .args(["--config", "-"])
+        .arg(&request.url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let mut child = command
+        .spawn()
+        .map_err(|err| format!("Failed to run curl, which is needed for https: {err}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(curl_config(request).as_bytes())
+            .map_err(|err| format!("Failed to send the request to curl: {err}"))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("Failed to run curl: {err}"))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Request to {} failed: {}",
+            request.url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+    let status = status
+        .trim()
+        .parse()
+        .map_err(|_| format!("curl did not report a status for {}", request.url))?;
+
+    Ok(Response {
+        status,
+        body: body.to_owned(),
+    })
+}
+
+fn curl_config(request: &Request) -> String {
+    let mut config = String::new();
+    for (name, value) in &request.headers {
+        config += &format!("header = {}\n", quote_config(&format!("{name}: {value}")));
+    }
+    if let Some(body) = &request.body {
+        // Unlike data-binary, data-raw never reads a file for a value starting with '@'.
+        config += &format!("data-raw = {}\n", quote_config(body));
+    }
+
+    config
+}
+
+fn quote_config(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for char in value.chars() {
+        match char {
+            '"' => quoted += "\\\"",
+            '\\' => quoted += "\\\\",
+            '\n' => quoted += "\\n",
+            '\r' => quoted += "\\r",
+            '\t' => quoted += "\\t",
+            other => quoted.push(other),
+        }
+    }
+    quoted.push('"');
+
+    quoted
+}
+
+/// A server on a free local port that answers every connection with the next of `responses`, and
+/// hands back the requests it received once it has answered all of them.
+#[cfg(test)]
+pub fn serve(responses: Vec<String>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let server = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for response in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 4096];
+            while !has_complete_request(&request) {
+                let read = stream.read(&mut buffer).unwrap();
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buffer[..read]);
+            }
+            stream.write_all(response.as_bytes()).unwrap();
+            requests.push(String::from_utf8_lossy(&request).into_owned());
+        }
+        requests
+    });
+
+    (url, server)
+}
+
+#[cfg(test)]
+fn has_complete_request(request: &[u8]) -> bool {
+    let text = String::from_utf8_lossy(request);
+    let Some((head, body)) = text.split_once("\r\n\r\n") else {
+        return false;
+    };
+    let length = head
+        .lines()
+        .find_map(|line| line.strip_prefix("Content-Length: "))
+        .and_then(|length| length.parse().ok())
+        .unwrap_or(0);
+
+    body.len() >= length
+}
+
+#[test]
+fn test_get_over_plain_http() {
+    let (url, server) = serve(vec![
+        "HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\n1\n2\n3".to_owned(),
+    ]);
+
+    let response =
+        send(&Request::get(&format!("{url}/2025/day/1/input")).header("Cookie", "a=b")).unwrap();
+    let requests = server.join().unwrap();
+
+    assert_eq!(
+        response,
+        Response {
+            status: 200,
+            body: "1\n2\n3".to_owned()
+        }
+    );
+    assert!(requests[0].starts_with("GET /2025/day/1/input HTTP/1.1\r\n"));
+    assert!(requests[0].contains(&format!("User-Agent: {USER_AGENT}\r\n")));
+    assert!(requests[0].contains("Cookie: a=b\r\n"));
+}
+
+#[test]
+fn test_post_over_plain_http() {
+    let (url, server) = serve(vec!["HTTP/1.1 404 Not Found\r\n\r\nnope".to_owned()]);
+
+    let response = send(&Request::post(&url, "level=1&answer=42".to_owned())).unwrap();
+    let requests = server.join().unwrap();
+
+    assert_eq!(response.status, 404);
+    assert_eq!(response.body, "nope");
+    assert!(requests[0].starts_with("POST / HTTP/1.1\r\n"));
+    assert!(requests[0].ends_with("Content-Length: 17\r\n\r\nlevel=1&answer=42"));
+}
+
+#[test]
+fn test_parse_chunked_response() {
+    let response = parse_response(
+        b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\n1\n2\n\r\n2\r\n3\n\r\n0\r\n\r\n",
+    )
+    .unwrap();
+
+    assert_eq!(response.body, "1\n2\n3\n");
+    assert!(parse_response(b"HTTP/1.1 200 OK\r\n").is_err());
+    assert!(send(&Request::get("ftp://example.com")).is_err());
+}
+
+#[test]
+fn test_content_length_counts_bytes() {
+    let response =
+        parse_response("HTTP/1.1 200 OK\r\nContent-Length: 3\r\n\r\né\n trailing".as_bytes())
+            .unwrap();
+
+    assert_eq!(response.body, "é\n");
+}
+
+#[test]
+fn test_curl_config_keeps_the_cookie_off_the_command_line() {
+    let request = Request::post("https://example.com", "level=1&answer=\"42\"".to_owned())
+        .header("Cookie", "session=secret");
+
+    assert_eq!(
+        curl_config(&request),
+        "header = \"Cookie: session=secret\"\ndata-raw = \"level=1&answer=\\\"42\\\"\"\n"
+    );
+}
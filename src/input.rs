@@ -0,0 +1,134 @@
+use crate::error::AocError;
+use crate::puzzle_input::PuzzleInput;
+use crate::solution::Part;
+use std::env;
+use std::ffi::OsString;
+use std::fs::read_to_string;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Environment variable that points at the directory holding the puzzle inputs.
+pub const INPUT_DIR_VARIABLE: &str = "AOC_INPUT_DIR";
+
+/// The path that reads the input from standard input instead of a file.
+pub const STDIN_PATH: &str = "-";
+
+/// Which of a day's input files to load.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputKind {
+    Example(Part),
+    Puzzle,
+}
+
+/// The directory the puzzle inputs and expected answers are read from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputDirectory {
+    root: PathBuf,
+}
+
+impl InputDirectory {
+    /// Uses the directory given on the command line, then `AOC_INPUT_DIR`, and otherwise the
+    /// `puzzle-inputs` directory of this crate, so the binary works from any working directory.
+    pub fn resolve(flag: Option<&Path>) -> InputDirectory {
+        resolve_from(flag, env::var_os(INPUT_DIR_VARIABLE))
+    }
+
+    /// Days that use a different example for each part store them as `day-N-example-P.txt`.
+    pub fn path(&self, day: u8, kind: InputKind) -> PathBuf {
+        match kind {
+            InputKind::Example(part) => {
+                let per_part = self.root.join(format!("day-{day}-example-{part}.txt"));
+                if per_part.exists() {
+                    return per_part;
+                }
+
+                self.root.join(format!("day-{day}-example.txt"))
+            }
+            InputKind::Puzzle => self.root.join(format!("day-{day}-input.txt")),
+        }
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        self.root.join("answers.txt")
+    }
+
+    /// Every answer sent with `submit`, see [crate::submit::History].
+    pub fn submissions_path(&self) -> PathBuf {
+        self.root.join("submissions.txt")
+    }
+
+    pub fn read(&self, day: u8, kind: InputKind) -> Result<PuzzleInput, AocError> {
+        let text = read_input(&self.path(day, kind))?;
+
+        Ok(match kind {
+            InputKind::Example(_) => PuzzleInput::example(&text),
+            InputKind::Puzzle => PuzzleInput::new(&text),
+        })
+    }
+}
+
+impl Default for InputDirectory {
+    fn default() -> Self {
+        InputDirectory::resolve(None)
+    }
+}
+
+fn resolve_from(flag: Option<&Path>, variable: Option<OsString>) -> InputDirectory {
+    let root = match (flag, variable) {
+        (Some(path), _) => path.to_path_buf(),
+        (None, Some(path)) if !path.is_empty() => PathBuf::from(path),
+        _ => Path::new(env!("CARGO_MANIFEST_DIR")).join("puzzle-inputs"),
+    };
+
+    InputDirectory { root }
+}
+
+pub fn read_input(path: &Path) -> Result<String, AocError> {
+    if path == Path::new(STDIN_PATH) {
+        let mut text = String::new();
+        return io::stdin()
+            .read_to_string(&mut text)
+            .map(|_| text)
+            .map_err(|err| AocError::Io(path.to_path_buf(), err));
+    }
+
+    read_to_string(path).map_err(|err| match err.kind() {
+        io::ErrorKind::NotFound => AocError::MissingInput(path.to_path_buf()),
+        _ => AocError::Io(path.to_path_buf(), err),
+    })
+}
+
+#[test]
+fn test_resolve_input_directory() {
+    let flag = Path::new("from/flag");
+
+    assert_eq!(
+        resolve_from(Some(flag), Some(OsString::from("from/env"))).root,
+        flag
+    );
+    assert_eq!(
+        resolve_from(None, Some(OsString::from("from/env"))).root,
+        Path::new("from/env")
+    );
+    assert!(resolve_from(None, None).root.is_absolute());
+    assert!(resolve_from(None, Some(OsString::new())).root.is_absolute());
+}
+
+#[test]
+fn test_input_paths() {
+    let root = env::temp_dir().join(format!("aoc-2025-inputs-{}", std::process::id()));
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(root.join("day-11-example-2.txt"), "").unwrap();
+    let directory = resolve_from(Some(&root), None);
+
+    let example_1 = directory.path(11, InputKind::Example(Part::One));
+    let example_2 = directory.path(11, InputKind::Example(Part::Two));
+    let puzzle = directory.path(11, InputKind::Puzzle);
+    let missing = directory.read(11, InputKind::Puzzle);
+    std::fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(example_1, root.join("day-11-example.txt"));
+    assert_eq!(example_2, root.join("day-11-example-2.txt"));
+    assert_eq!(puzzle, root.join("day-11-input.txt"));
+    assert!(matches!(missing, Err(AocError::MissingInput(path)) if path == puzzle));
+}
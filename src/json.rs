@@ -0,0 +1,347 @@
+use std::fmt::{Display, Formatter, Write};
+
+/// A minimal JSON document model, enough to write and read back the files this tool produces.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    /// Keys keep their insertion order, so written files are stable between runs.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields
+                .iter()
+                .find(|(field, _)| field == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Writes arrays with one element per line, which keeps files that are checked in diffable.
+    pub fn to_string_pretty(&self) -> String {
+        match self {
+            Json::Array(values) if !values.is_empty() => {
+                let values: Vec<String> = values.iter().map(|value| format!("  {value}")).collect();
+                format!("[\n{}\n]", values.join(",\n"))
+            }
+            Json::Object(fields) if !fields.is_empty() => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(key, value)| {
+                        let value = value.to_string_pretty().replace('\n', "\n  ");
+                        format!("  {}: {value}", Json::String(key.clone()))
+                    })
+                    .collect();
+                format!("{{\n{}\n}}", fields.join(",\n"))
+            }
+            other => other.to_string(),
+        }
+    }
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(value) => write!(f, "{value}"),
+            Json::Number(number) if number.is_finite() => write!(f, "{number}"),
+            Json::Number(_) => f.write_str("null"),
+            Json::String(string) => {
+                f.write_char('"')?;
+                for character in string.chars() {
+                    match character {
+                        '"' => f.write_str("\\\"")?,
+                        '\\' => f.write_str("\\\\")?,
+                        '\n' => f.write_str("\\n")?,
+                        '\r' => f.write_str("\\r")?,
+                        '\t' => f.write_str("\\t")?,
+                        c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+                        c => f.write_char(c)?,
+                    }
+                }
+                f.write_char('"')
+            }
+            Json::Array(values) => {
+                f.write_char('[')?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_char(']')
+            }
+            Json::Object(fields) => {
+                f.write_char('{')?;
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}:{value}", Json::String(key.clone()))?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+pub fn parse(text: &str) -> Result<Json, String> {
+    let mut parser = Parser {
+        characters: text.chars().collect(),
+        position: 0,
+    };
+
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+    if parser.position < parser.characters.len() {
+        return Err(parser.error("Unexpected trailing characters"));
+    }
+
+    Ok(value)
+}
+
+struct Parser {
+    characters: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn parse_value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some('n') => self.parse_literal("null", Json::Null),
+            Some('t') => self.parse_literal("true", Json::Bool(true)),
+            Some('f') => self.parse_literal("false", Json::Bool(false)),
+            Some('"') => self.parse_string().map(Json::String),
+            Some('[') => self.parse_array(),
+            Some('{') => self.parse_object(),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.parse_number(),
+            Some(c) => Err(self.error(&format!("Unexpected character '{c}'"))),
+            None => Err(self.error("Unexpected end of input")),
+        }
+    }
+
+    fn parse_literal(&mut self, literal: &str, value: Json) -> Result<Json, String> {
+        for expected in literal.chars() {
+            if self.next() != Some(expected) {
+                return Err(self.error(&format!("Expected '{literal}'")));
+            }
+        }
+
+        Ok(value)
+    }
+
+    fn parse_number(&mut self) -> Result<Json, String> {
+        let start = self.position;
+        while let Some(c) = self.peek() {
+            if c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E') {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+
+        let text: String = self.characters[start..self.position].iter().collect();
+        text.parse::<f64>()
+            .map(Json::Number)
+            .map_err(|_| self.error(&format!("Invalid number '{text}'")))
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(string),
+                Some('\\') => match self.next() {
+                    Some('"') => string.push('"'),
+                    Some('\\') => string.push('\\'),
+                    Some('/') => string.push('/'),
+                    Some('n') => string.push('\n'),
+                    Some('r') => string.push('\r'),
+                    Some('t') => string.push('\t'),
+                    Some('b') => string.push('\u{8}'),
+                    Some('f') => string.push('\u{c}'),
+                    Some('u') => {
+                        let hex: String = (0..4).filter_map(|_| self.next()).collect();
+                        let character = u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| self.error(&format!("Invalid escape '\\u{hex}'")))?;
+                        string.push(character);
+                    }
+                    _ => return Err(self.error("Invalid escape sequence")),
+                },
+                Some(c) => string.push(c),
+                None => return Err(self.error("Unterminated string")),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Json, String> {
+        self.expect('[')?;
+
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.position += 1;
+            return Ok(Json::Array(values));
+        }
+
+        loop {
+            values.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(Json::Array(values)),
+                _ => return Err(self.error("Expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Json, String> {
+        self.expect('{')?;
+
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(Json::Object(fields));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            fields.push((key, self.parse_value()?));
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(Json::Object(fields)),
+                _ => return Err(self.error("Expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            _ => Err(self.error(&format!("Expected '{expected}'"))),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.characters.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let character = self.peek();
+        self.position += 1;
+        character
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("{message} at character {}", self.position)
+    }
+}
+
+#[test]
+fn test_write_json() {
+    let json = Json::Object(vec![
+        ("day".to_owned(), Json::Number(7.0)),
+        (
+            "answer".to_owned(),
+            Json::String("a \"quoted\"\n".to_owned()),
+        ),
+        (
+            "samples".to_owned(),
+            Json::Array(vec![Json::Number(1.5), Json::Null, Json::Bool(true)]),
+        ),
+    ]);
+
+    assert_eq!(
+        json.to_string(),
+        r#"{"day":7,"answer":"a \"quoted\"\n","samples":[1.5,null,true]}"#
+    );
+}
+
+#[test]
+fn test_parse_json() {
+    let json = parse(
+        r#" { "day": 7, "name": "tab\tbed A", "values": [1, -2.5e3, false, null], "empty": {} } "#,
+    )
+    .unwrap();
+
+    assert_eq!(json.get("day").and_then(Json::as_f64), Some(7.0));
+    assert_eq!(json.get("name").and_then(Json::as_str), Some("tab\tbed A"));
+    assert_eq!(
+        json.get("values").and_then(Json::as_array),
+        Some(
+            &[
+                Json::Number(1.0),
+                Json::Number(-2500.0),
+                Json::Bool(false),
+                Json::Null
+            ][..]
+        )
+    );
+    assert_eq!(json.get("empty"), Some(&Json::Object(vec![])));
+}
+
+#[test]
+fn test_pretty_json_round_trips() {
+    let json = Json::Object(vec![(
+        "benchmarks".to_owned(),
+        Json::Array(vec![
+            Json::Object(vec![("day".to_owned(), Json::Number(1.0))]),
+            Json::Object(vec![("day".to_owned(), Json::Number(2.0))]),
+        ]),
+    )]);
+
+    assert_eq!(parse(&json.to_string_pretty()), Ok(json));
+}
+
+#[test]
+fn test_parse_invalid_json() {
+    assert!(parse("").is_err());
+    assert!(parse("{").is_err());
+    assert!(parse("[1,]").is_err());
+    assert!(parse("\"unterminated").is_err());
+    assert!(parse("{} extra").is_err());
+}
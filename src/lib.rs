@@ -0,0 +1,45 @@
+//! Solutions to Advent of Code 2025, along with the tooling to run, benchmark and verify them.
+//!
+//! Every day implements [solution::Solution] and is listed in [registry::DAYS]. The `aoc-2025`
+//! binary is a command line interface on top of this library.
+//!
+//! With the `count-allocations` feature the binary installs [memory::CountingAllocator], and every
+//! timed phase also reports the memory it allocated, see [memory::measure].
+
+pub mod answers;
+pub mod baseline;
+pub mod cancel;
+pub mod cli;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod error;
+pub mod export;
+pub mod fetch;
+pub mod fuzz;
+pub mod http;
+pub mod input;
+pub mod json;
+pub mod log;
+pub mod memory;
+pub mod output;
+pub mod puzzle_input;
+pub mod registry;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod scoped;
+pub mod solution;
+pub mod stopwatch;
+pub mod submit;
+pub mod verify;
+pub mod visualize;
+pub mod watch;
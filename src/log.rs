@@ -0,0 +1,102 @@
+use crate::scoped::{self, Slot};
+use std::cell::RefCell;
+use std::fmt::{Arguments, Write};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much diagnostic output is printed. Every level also prints the messages of the levels
+/// before it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Only errors and the final results.
+    Quiet,
+    /// Timings and a sentence about every answer.
+    #[default]
+    Info,
+    /// A line for every step of a solution, set with `-v`.
+    Debug,
+    /// Everything the solutions can tell about what they do, set with `-vv`.
+    Trace,
+}
+
+impl Level {
+    const ALL: [Level; 4] = [Level::Quiet, Level::Info, Level::Debug, Level::Trace];
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Info as u8);
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    Level::ALL[LEVEL.load(Ordering::Relaxed) as usize]
+}
+
+pub fn enabled(level: Level) -> bool {
+    level <= self::level()
+}
+
+thread_local! {
+    static CAPTURED: Slot<String> = const { RefCell::new(None) };
+}
+
+/// Writes a line to stderr, so the output of the commands stays untouched, or to the buffer of the
+/// [capture] this thread is in.
+pub fn write(message: Arguments) {
+    let written = scoped::get(&CAPTURED, |buffer| {
+        let _ = writeln!(buffer, "{message}");
+    });
+    if written.is_none() {
+        eprintln!("{message}");
+    }
+}
+
+/// Runs `function` while holding back everything it logs on this thread, and returns that output
+/// next to its result. Used to keep the output of work that runs in parallel from interleaving.
+pub fn capture<T>(function: impl FnOnce() -> T) -> (T, String) {
+    let (result, captured) = scoped::with(&CAPTURED, Some(String::new()), function);
+
+    (result, captured.unwrap_or_default())
+}
+
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        if $crate::log::enabled($level) {
+            $crate::log::write(format_args!($($arg)*));
+        }
+    };
+}
+
+macro_rules! info {
+    ($($arg:tt)*) => { $crate::log::log!($crate::log::Level::Info, $($arg)*) };
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => { $crate::log::log!($crate::log::Level::Debug, $($arg)*) };
+}
+
+macro_rules! trace {
+    ($($arg:tt)*) => { $crate::log::log!($crate::log::Level::Trace, $($arg)*) };
+}
+
+pub(crate) use {debug, info, log, trace};
+
+#[test]
+fn test_levels_include_the_levels_before_them() {
+    assert!(Level::Quiet < Level::Info);
+    assert!(Level::Debug < Level::Trace);
+    assert_eq!(Level::ALL.map(|level| level as u8), [0, 1, 2, 3]);
+}
+
+#[test]
+fn test_capture_holds_back_output() {
+    let (result, captured) = capture(|| {
+        write(format_args!("outer {}", 1));
+        let (_, inner) = capture(|| write(format_args!("inner")));
+        write(format_args!("outer {}", 2));
+        inner
+    });
+
+    assert_eq!(result, "inner\n");
+    assert_eq!(captured, "outer 1\nouter 2\n");
+}
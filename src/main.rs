@@ -1,44 +1,467 @@
-extern crate core;
-
+use aoc_2025::baseline::{self, Verdict};
+use aoc_2025::cli::{
+    self, Command, DaySelection, ExportOptions, FuzzOptions, SubmitOptions, VisualizeOptions,
+};
+use aoc_2025::error::AocError;
+use aoc_2025::fetch::{self, Fetched, Session};
+use aoc_2025::input::{InputDirectory, InputKind};
+#[cfg(feature = "count-allocations")]
+use aoc_2025::memory;
+use aoc_2025::output::{self, OutputFormat, Record, Status};
+use aoc_2025::report::{self, BenchmarkRow};
+use aoc_2025::runner::{self, Input, Measurement};
+use aoc_2025::solution::Day;
+use aoc_2025::submit::{self, History};
+use aoc_2025::{answers, export, fuzz, log, registry, scaffold, verify, visualize, watch};
 use std::env;
+use std::io;
+use std::panic;
+use std::path::Path;
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-mod day1;
-mod day10;
-mod day11;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod stopwatch;
-
-fn main() {
-    let mut args = env::args();
-    let first_argument = args.nth(1);
-
-    let first_argument = match first_argument {
-        Some(arg) => arg,
-        None => {
-            println!("Please enter a day number");
-            return;
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: memory::CountingAllocator = memory::CountingAllocator;
+
+fn main() -> ExitCode {
+    let (global, command) = match cli::parse_command_line(env::args().skip(1)) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("{message}\n\n{}", cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    log::set_level(global.verbosity);
+    let directory = InputDirectory::resolve(global.input_dir.as_deref());
+
+    match command {
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            ExitCode::SUCCESS
+        }
+        Command::Run(options) => {
+            let days = match selected_days(&options.days) {
+                Ok(days) => days,
+                Err(message) => {
+                    eprintln!("{message}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let mut measurements: Vec<Measurement> = Vec::new();
+            let mut failed = false;
+            for run in runner::run(&days, &options, &directory) {
+                eprint!("{}", run.log);
+                match run.result {
+                    Ok(day_measurements) => measurements.extend(day_measurements),
+                    Err(err) => {
+                        eprintln!("Day {}: {err}", run.day);
+                        failed = true;
+                    }
+                }
+            }
+
+            failed |= measurements
+                .iter()
+                .any(|measurement| measurement.answer.is_none());
+            if failed && measurements.is_empty() {
+                return ExitCode::FAILURE;
+            }
+            if let Err(message) = print_measurements(&measurements, global.format, &directory) {
+                eprintln!("{message}");
+                failed = true;
+            }
+            if failed {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Command::Bench(options) => {
+            if global.format != OutputFormat::Text {
+                eprintln!(
+                    "bench does not support --format, use --save to write the timings as JSON"
+                );
+                return ExitCode::from(2);
+            }
+
+            let days = match selected_days(&options.run.days) {
+                Ok(days) => days,
+                Err(message) => {
+                    eprintln!("{message}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let mut rows: Vec<BenchmarkRow> = Vec::new();
+            for day in days {
+                match runner::bench(day, &options.run, &options.benchmark, &directory) {
+                    Ok(day_rows) => rows.extend(day_rows),
+                    Err(err) => {
+                        eprintln!("Day {}: {err}", day.number());
+                        return ExitCode::FAILURE;
+                    }
+                }
+            }
+
+            report::print_benchmarks(&rows);
+
+            if let Some(path) = &options.save
+                && let Err(message) = baseline::save_baseline(path, &rows)
+            {
+                eprintln!("{message}");
+                return ExitCode::FAILURE;
+            }
+
+            let Some(path) = &options.compare else {
+                return ExitCode::SUCCESS;
+            };
+            let baseline = match baseline::read_baseline(path) {
+                Ok(baseline) => baseline,
+                Err(message) => {
+                    eprintln!("{message}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let comparisons = baseline::compare(&baseline, &rows, options.threshold_percent);
+            report::print_comparisons(&comparisons);
+
+            if comparisons
+                .iter()
+                .any(|comparison| comparison.verdict == Verdict::Regressed)
+            {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Command::Watch(options) => {
+            let DaySelection::Single(number) = options.run.days else {
+                unreachable!("watch is parsed with a single day");
+            };
+            match find_day(number) {
+                Ok(day) => watch::watch(day, &options, &directory),
+                Err(message) => {
+                    eprintln!("{message}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Visualize(options) => {
+            let DaySelection::Single(number) = options.run.days else {
+                unreachable!("visualize is parsed with a single day");
+            };
+            let day = match find_day(number) {
+                Ok(day) => day,
+                Err(message) => {
+                    eprintln!("{message}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            match play_frames(day, &options, &directory) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(message) => {
+                    eprintln!("{message}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Export(options) => {
+            let days = match selected_days(&options.run.days) {
+                Ok(days) => days,
+                Err(message) => {
+                    eprintln!("{message}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let mut failed = false;
+            for day in days {
+                if let Err(message) = export_pictures(day, &options, &directory) {
+                    eprintln!("{message}");
+                    failed = true;
+                }
+            }
+            if failed {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Command::New(day) => {
+            if registry::find(day).is_some() {
+                eprintln!("Day {day} already exists");
+                return ExitCode::FAILURE;
+            }
+
+            match scaffold::new_day(day, Path::new("src"), &directory) {
+                Ok(written) => {
+                    for path in written {
+                        println!("Wrote {}", path.display());
+                    }
+                    println!("Day {day} is registered, build again to run it");
+                    ExitCode::SUCCESS
+                }
+                Err(message) => {
+                    eprintln!("{message}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Fetch(options) => {
+            let base_url = fetch::base_url(options.base_url.as_deref());
+            let session = || Session::resolve(options.session_file.as_deref());
+
+            match fetch::fetch(options.day, &directory, &base_url, session) {
+                Ok(Fetched::Downloaded(path)) => {
+                    println!("Downloaded day {} to {}", options.day, path.display());
+                    ExitCode::SUCCESS
+                }
+                Ok(Fetched::Cached(path)) => {
+                    println!("Day {} is already at {}", options.day, path.display());
+                    ExitCode::SUCCESS
+                }
+                Err(message) => {
+                    eprintln!("{message}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Submit(options) => {
+            let day = match find_day(options.day) {
+                Ok(day) => day,
+                Err(message) => {
+                    eprintln!("{message}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            match submit_answer(day, &options, &directory) {
+                Ok(submit::Verdict::Correct) => ExitCode::SUCCESS,
+                Ok(_) => ExitCode::FAILURE,
+                Err(message) => {
+                    eprintln!("{message}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Fuzz(options) => match fuzz_day(&options) {
+            Ok(true) => ExitCode::SUCCESS,
+            Ok(false) => ExitCode::FAILURE,
+            Err(message) => {
+                eprintln!("{message}");
+                ExitCode::FAILURE
+            }
+        },
+        Command::Verify(options) => {
+            let answers = match answers::read_answers(&directory.answers_path()) {
+                Ok(answers) => answers,
+                Err(message) => {
+                    eprintln!("{message}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let days = match selected_days(&options.days) {
+                Ok(days) => days,
+                Err(message) => {
+                    eprintln!("{message}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let records = verify::verify(&days, &answers, options.timeout, &directory);
+            match global.format {
+                OutputFormat::Text => verify::print_results(&records),
+                format => output::print_records(&records, format),
+            }
+
+            if records
+                .iter()
+                .all(|record| !matches!(record.status, Status::Fail | Status::Timeout))
+            {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+    }
+}
+
+/// The registered days the selection asks for.
+fn selected_days(days: &DaySelection) -> Result<Vec<&'static dyn Day>, String> {
+    match days {
+        DaySelection::All => Ok(registry::DAYS.to_vec()),
+        DaySelection::Single(number) => find_day(*number).map(|day| vec![day]),
+    }
+}
+
+fn find_day(number: u8) -> Result<&'static dyn Day, String> {
+    registry::find(number).ok_or_else(|| format!("Day {number} has not been solved yet"))
+}
+
+/// Solves the part against the puzzle input and sends the answer, waiting out any wait that is left
+/// from the previous answer.
+fn submit_answer(
+    day: &dyn Day,
+    options: &SubmitOptions,
+    directory: &InputDirectory,
+) -> Result<submit::Verdict, String> {
+    let input = Input {
+        name: "Puzzle".to_owned(),
+        path: directory.path(options.day, InputKind::Puzzle),
+        parts: vec![options.part],
+        example: false,
+    };
+    let measurements = runner::run_input(day, &input, None).map_err(|err| err.to_string())?;
+    let answer = measurements
+        .first()
+        .and_then(|measurement| measurement.answer.as_ref())
+        .ok_or("The part did not run")?;
+
+    let session = Session::resolve(options.session_file.as_deref())?;
+    let base_url = fetch::base_url(options.base_url.as_deref());
+    let mut history = History::read(&directory.submissions_path())?;
+    let sleep = |wait: Duration| {
+        eprintln!("Waiting {}s before submitting", wait.as_secs());
+        thread::sleep(wait);
+    };
+
+    println!(
+        "Submitting {answer} for day {} part {}",
+        options.day, options.part
+    );
+    let outcome = submit::submit(
+        options.day,
+        options.part,
+        answer,
+        &base_url,
+        &session,
+        &mut history,
+        sleep,
+    )?;
+
+    println!("{}", outcome.verdict);
+    if !outcome.wait.is_zero() {
+        println!("The next answer can be sent in {}s", outcome.wait.as_secs());
+    }
+    Ok(outcome.verdict)
+}
+
+/// Runs the day against every selected input while recording its frames, and plays them.
+fn play_frames(
+    day: &dyn Day,
+    options: &VisualizeOptions,
+    directory: &InputDirectory,
+) -> Result<(), String> {
+    let parts = runner::selected_parts(&options.run);
+    for input in runner::inputs(day.number(), &parts, &options.run.inputs, directory) {
+        let (result, frames) =
+            visualize::record(|| runner::run_input(day, &input, options.run.timeout));
+        result.map_err(|err| err.to_string())?;
+
+        if frames.is_empty() {
+            println!("Day {} draws no frames for {}", day.number(), input.name);
+            continue;
+        }
+        visualize::play(
+            &frames,
+            options.pace,
+            &mut io::stdout().lock(),
+            &mut io::stdin().lock(),
+        )
+        .map_err(|err| format!("Failed to draw the frames: {err}"))?;
+    }
+
+    Ok(())
+}
+
+/// Runs the day against every selected input while recording its pictures, and writes them.
+fn export_pictures(
+    day: &dyn Day,
+    options: &ExportOptions,
+    directory: &InputDirectory,
+) -> Result<(), String> {
+    let parts = runner::selected_parts(&options.run);
+    for input in runner::inputs(day.number(), &parts, &options.run.inputs, directory) {
+        let (result, pictures) =
+            export::record(|| runner::run_input(day, &input, options.run.timeout));
+        match result {
+            Ok(_) => {}
+            Err(AocError::MissingInput(_)) if options.run.days == DaySelection::All => continue,
+            Err(err) => return Err(err.to_string()),
         }
+
+        let prefix = format!("day-{}-{}", day.number(), input.name);
+        for path in export::write_pictures(&pictures, &options.output_dir, &prefix, options.scale)?
+        {
+            println!("Wrote {}", path.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the day against its brute-force solution, and returns whether they agreed on every input.
+fn fuzz_day(options: &FuzzOptions) -> Result<bool, String> {
+    let (Some(day), Some(target)) = (registry::find(options.day), fuzz::find(options.day)) else {
+        let days: Vec<String> = fuzz::TARGETS
+            .iter()
+            .map(|target| target.day.to_string())
+            .collect();
+        return Err(format!(
+            "Day {} cannot be fuzzed, only days {} can",
+            options.day,
+            days.join(", ")
+        ));
     };
+    let seed = options.seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_nanos() as u64)
+    });
+
+    println!(
+        "Fuzzing day {} with {} inputs from seed {seed}",
+        options.day, options.iterations
+    );
+    // Panics of the day are reported as a mismatch, so their messages would only get in the way.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let mismatch = fuzz::fuzz(day, target, seed, options.iterations);
+    panic::set_hook(hook);
 
-    match first_argument.as_str() {
-        "1" => day1::run(),
-        "2" => day2::run(),
-        "3" => day3::run(),
-        "4" => day4::run(),
-        "5" => day5::run(),
-        "6" => day6::run(),
-        "7" => day7::run(),
-        "8" => day8::run(),
-        "9" => day9::run(),
-        "10" => day10::run(),
-        "11" => day11::run(),
-        n => println!("Unknown day: {}", n),
+    match mismatch {
+        None => {
+            println!("The day agreed with the brute-force solution on every input");
+            Ok(true)
+        }
+        Some(mismatch) => {
+            println!("{mismatch}");
+            Ok(false)
+        }
+    }
+}
+
+/// Prints the measurements as a table, or as records that are checked against the expected answers.
+fn print_measurements(
+    measurements: &[Measurement],
+    format: OutputFormat,
+    directory: &InputDirectory,
+) -> Result<(), String> {
+    if format == OutputFormat::Text {
+        report::print_summary(measurements);
+        return Ok(());
     }
+
+    let answers = answers::read_answers_if_present(&directory.answers_path())?;
+    let records: Vec<Record> = measurements
+        .iter()
+        .map(|measurement| Record::checked(measurement, &answers))
+        .collect();
+    output::print_records(&records, format);
+
+    Ok(())
 }
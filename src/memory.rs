@@ -0,0 +1,208 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Forwards to the system allocator while counting what every thread allocates. The binary installs
+/// it as the global allocator when it is built with the `count-allocations` feature, other users of
+/// the library can install it themselves.
+pub struct CountingAllocator;
+
+/// Set by the first allocation the [CountingAllocator] counts, so [measure] knows it is installed.
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = unsafe { System.alloc(layout) };
+        if !pointer.is_null() {
+            installed();
+            allocated(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let pointer = unsafe { System.alloc_zeroed(layout) };
+        if !pointer.is_null() {
+            installed();
+            allocated(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(pointer, layout) };
+        freed(layout.size());
+    }
+
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_pointer = unsafe { System.realloc(pointer, layout, new_size) };
+        if !new_pointer.is_null() {
+            freed(layout.size());
+            allocated(new_size);
+        }
+        new_pointer
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Counters {
+    /// Bytes allocated on this thread and not freed yet. Memory freed by another thread than the one
+    /// that allocated it is not subtracted from this one, so this is an estimate.
+    live: usize,
+    peak: usize,
+    total: usize,
+    allocations: usize,
+}
+
+thread_local! {
+    // Const initialized and without a destructor, so using it never allocates.
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            live: 0,
+            peak: 0,
+            total: 0,
+            allocations: 0,
+        })
+    };
+}
+
+fn update(change: impl FnOnce(&mut Counters)) {
+    // Fails while the thread is being torn down, those allocations are not counted.
+    let _ = COUNTERS.try_with(|counters| {
+        let mut updated = counters.get();
+        change(&mut updated);
+        counters.set(updated);
+    });
+}
+
+fn installed() {
+    // Loaded first, so allocations do not all write to the same cache line.
+    if !INSTALLED.load(Ordering::Relaxed) {
+        INSTALLED.store(true, Ordering::Relaxed);
+    }
+}
+
+fn allocated(size: usize) {
+    update(|counters| {
+        counters.live += size;
+        counters.peak = counters.peak.max(counters.live);
+        counters.total += size;
+        counters.allocations += 1;
+    });
+}
+
+fn freed(size: usize) {
+    update(|counters| counters.live = counters.live.saturating_sub(size));
+}
+
+/// What one phase allocated on its thread.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct MemoryUsage {
+    /// The most memory the phase held at once, on top of what was allocated before it started.
+    pub peak_bytes: usize,
+    /// Everything the phase allocated, including memory it freed again.
+    pub total_bytes: usize,
+    pub allocations: usize,
+}
+
+impl Display for MemoryUsage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "peak {}, {} allocations of {} in total",
+            format_bytes(self.peak_bytes),
+            self.allocations,
+            format_bytes(self.total_bytes)
+        )
+    }
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut amount = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while amount >= 1024.0 && unit < UNITS.len() - 1 {
+        amount /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{amount:.1} {}", UNITS[unit])
+}
+
+/// Runs `function` and returns what it allocated on this thread, or `None` when allocations are not
+/// counted because the [CountingAllocator] is not installed.
+pub fn measure<T>(function: impl FnOnce() -> T) -> (T, Option<MemoryUsage>) {
+    if INSTALLED.load(Ordering::Relaxed) {
+        let (result, usage) = count(function);
+        (result, Some(usage))
+    } else {
+        (function(), None)
+    }
+}
+
+fn count<T>(function: impl FnOnce() -> T) -> (T, MemoryUsage) {
+    let before = COUNTERS.get();
+    // Peaks are counted from what is live now, the peak before is put back for an outer count.
+    COUNTERS.set(Counters {
+        peak: before.live,
+        ..before
+    });
+    let result = function();
+    let after = COUNTERS.get();
+    COUNTERS.set(Counters {
+        peak: after.peak.max(before.peak),
+        ..after
+    });
+
+    let usage = MemoryUsage {
+        peak_bytes: after.peak - before.live,
+        total_bytes: after.total - before.total,
+        allocations: after.allocations - before.allocations,
+    };
+    (result, usage)
+}
+
+#[test]
+fn test_count_allocations() {
+    let ((_, inner), outer) = count(|| {
+        allocated(100);
+        let inner = count(|| {
+            allocated(50);
+            freed(50);
+        });
+        freed(100);
+        allocated(10);
+        inner
+    });
+
+    assert_eq!(
+        inner,
+        MemoryUsage {
+            peak_bytes: 50,
+            total_bytes: 50,
+            allocations: 1
+        }
+    );
+    assert_eq!(
+        outer,
+        MemoryUsage {
+            peak_bytes: 150,
+            total_bytes: 160,
+            allocations: 3
+        }
+    );
+    freed(10);
+}
+
+#[test]
+fn test_format_bytes() {
+    assert_eq!(format_bytes(0), "0 B");
+    assert_eq!(format_bytes(1023), "1023 B");
+    assert_eq!(format_bytes(1536), "1.5 KiB");
+    assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+}
@@ -0,0 +1,258 @@
+use crate::answers::Answers;
+use crate::error::AocError;
+use crate::json::Json;
+use crate::memory::MemoryUsage;
+use crate::runner::Measurement;
+use crate::solution::Part;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How the results of a command are printed.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    /// Aligned tables meant for people.
+    #[default]
+    Text,
+    /// An array with one object per record.
+    Json,
+    /// A header followed by one line per record.
+    Csv,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            other => Err(format!(
+                "Invalid format '{other}', expected text, json or csv"
+            )),
+        }
+    }
+}
+
+/// Whether an answer matches the one in `answers.txt`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// There is no expected answer to compare with, or no input to run against.
+    Missing,
+    /// The part was cancelled because it ran past its timeout.
+    Timeout,
+}
+
+impl Status {
+    fn name(&self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail => "fail",
+            Status::Missing => "missing",
+            Status::Timeout => "timeout",
+        }
+    }
+}
+
+const COLUMNS: [&str; 15] = [
+    "day",
+    "part",
+    "input",
+    "answer",
+    "expected",
+    "parse_ns",
+    "solve_ns",
+    "status",
+    "error",
+    "parse_peak_bytes",
+    "parse_allocated_bytes",
+    "parse_allocations",
+    "solve_peak_bytes",
+    "solve_allocated_bytes",
+    "solve_allocations",
+];
+
+/// The result of one part of a day against one input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub answer: Option<String>,
+    pub expected: Option<String>,
+    pub parse_duration: Option<Duration>,
+    pub solve_duration: Option<Duration>,
+    pub status: Status,
+    /// Why the part did not produce an answer.
+    pub error: Option<String>,
+    /// Only set when allocations are counted.
+    pub parse_memory: Option<MemoryUsage>,
+    pub solve_memory: Option<MemoryUsage>,
+}
+
+impl Record {
+    /// A record for the measurement, with its answer checked against the expected answers.
+    pub fn checked(measurement: &Measurement, answers: &Answers) -> Record {
+        let expected = answers
+            .get(measurement.day, measurement.part, &measurement.input)
+            .map(str::to_owned);
+        let status = match (&measurement.answer, &expected) {
+            (None, _) => Status::Timeout,
+            (Some(answer), Some(expected)) if answer == expected => Status::Pass,
+            (Some(_), Some(_)) => Status::Fail,
+            (Some(_), None) => Status::Missing,
+        };
+
+        Record {
+            day: measurement.day,
+            part: measurement.part,
+            input: measurement.input.clone(),
+            answer: measurement.answer.clone(),
+            expected,
+            parse_duration: measurement.parse_duration,
+            solve_duration: Some(measurement.solve_duration),
+            status,
+            error: (status == Status::Timeout).then(|| AocError::Cancelled.to_string()),
+            parse_memory: measurement.parse_memory,
+            solve_memory: measurement.solve_memory,
+        }
+    }
+
+    /// The values of the [COLUMNS].
+    fn values(&self) -> Vec<Json> {
+        let string = |value: &Option<String>| value.clone().map_or(Json::Null, Json::String);
+        let nanoseconds = |duration: Option<Duration>| {
+            duration.map_or(Json::Null, |duration| {
+                Json::Number(duration.as_nanos() as f64)
+            })
+        };
+
+        let mut values = vec![
+            Json::Number(self.day.into()),
+            Json::Number(self.part.number().into()),
+            Json::String(self.input.clone()),
+            string(&self.answer),
+            string(&self.expected),
+            nanoseconds(self.parse_duration),
+            nanoseconds(self.solve_duration),
+            Json::String(self.status.name().to_owned()),
+            string(&self.error),
+        ];
+
+        for memory in [self.parse_memory, self.solve_memory] {
+            values.extend(match memory {
+                Some(usage) => [usage.peak_bytes, usage.total_bytes, usage.allocations]
+                    .map(|count| Json::Number(count as f64)),
+                None => [Json::Null, Json::Null, Json::Null],
+            });
+        }
+
+        values
+    }
+}
+
+/// Prints the records in a machine-readable format. Text output is left to the commands, which
+/// each print their own table.
+pub fn print_records(records: &[Record], format: OutputFormat) {
+    match format {
+        OutputFormat::Text => {}
+        OutputFormat::Json => println!("{}", to_json(records).to_string_pretty()),
+        OutputFormat::Csv => print!("{}", to_csv(records)),
+    }
+}
+
+fn to_json(records: &[Record]) -> Json {
+    Json::Array(
+        records
+            .iter()
+            .map(|record| {
+                Json::Object(
+                    COLUMNS
+                        .iter()
+                        .map(|column| column.to_string())
+                        .zip(record.values())
+                        .collect(),
+                )
+            })
+            .collect(),
+    )
+}
+
+fn to_csv(records: &[Record]) -> String {
+    let mut csv = COLUMNS.join(",") + "\n";
+    for record in records {
+        let cells: Vec<String> = record
+            .values()
+            .into_iter()
+            .map(|value| match value {
+                Json::Null => String::new(),
+                Json::String(text) => csv_cell(&text),
+                other => other.to_string(),
+            })
+            .collect();
+        csv += &(cells.join(",") + "\n");
+    }
+
+    csv
+}
+
+/// Quotes cells that contain separators, quotes or line breaks, doubling the quotes inside them.
+fn csv_cell(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_owned()
+    }
+}
+
+#[cfg(test)]
+fn record(answer: &str, status: Status) -> Record {
+    Record {
+        day: 1,
+        part: Part::Two,
+        input: "Example".to_owned(),
+        answer: Some(answer.to_owned()),
+        expected: Some("6".to_owned()),
+        parse_duration: None,
+        solve_duration: Some(Duration::from_micros(4)),
+        status,
+        error: None,
+        parse_memory: None,
+        solve_memory: None,
+    }
+}
+
+#[test]
+fn test_records_to_json() {
+    let json = to_json(&[record("6", Status::Pass)]);
+
+    assert_eq!(
+        json.to_string(),
+        r#"[{"day":1,"part":2,"input":"Example","answer":"6","expected":"6","parse_ns":null,"solve_ns":4000,"status":"pass","error":null,"parse_peak_bytes":null,"parse_allocated_bytes":null,"parse_allocations":null,"solve_peak_bytes":null,"solve_allocated_bytes":null,"solve_allocations":null}]"#
+    );
+}
+
+#[test]
+fn test_records_to_csv() {
+    let measured = Record {
+        solve_memory: Some(MemoryUsage {
+            peak_bytes: 2048,
+            total_bytes: 4096,
+            allocations: 3,
+        }),
+        ..record("6", Status::Pass)
+    };
+    let csv = to_csv(&[measured, record("1,\"2\"", Status::Fail)]);
+
+    assert_eq!(
+        csv,
+        "day,part,input,answer,expected,parse_ns,solve_ns,status,error,parse_peak_bytes,\
+         parse_allocated_bytes,parse_allocations,solve_peak_bytes,solve_allocated_bytes,\
+         solve_allocations\n\
+         1,2,Example,6,6,,4000,pass,,,,,2048,4096,3\n\
+         1,2,Example,\"1,\"\"2\"\"\",6,,4000,fail,,,,,,,\n"
+    );
+    assert_eq!(to_csv(&[]), COLUMNS.join(",") + "\n");
+}
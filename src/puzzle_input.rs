@@ -0,0 +1,191 @@
+use crate::error::{AocError, ParseError};
+
+/// Puzzle input text with `\r\n` line endings turned into `\n` and trailing newlines removed, so
+/// parsers behave the same no matter which platform the file was saved on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PuzzleInput {
+    text: String,
+    example: bool,
+}
+
+impl PuzzleInput {
+    pub fn new(raw: &str) -> PuzzleInput {
+        PuzzleInput {
+            text: raw.replace("\r\n", "\n").trim_end_matches('\n').to_owned(),
+            example: false,
+        }
+    }
+
+    /// An example from the puzzle text, for days where the examples ask for something else than
+    /// the puzzle input does.
+    pub fn example(raw: &str) -> PuzzleInput {
+        PuzzleInput {
+            example: true,
+            ..PuzzleInput::new(raw)
+        }
+    }
+
+    pub fn is_example(&self) -> bool {
+        self.example
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text.lines()
+    }
+
+    /// The blocks of lines that are separated by blank lines.
+    pub fn sections(&self) -> impl Iterator<Item = Section<'_>> {
+        let mut next_line = 0;
+
+        self.text.split("\n\n").filter_map(move |block| {
+            let first_line = next_line + block.len() - block.trim_start_matches('\n').len();
+            next_line += block.matches('\n').count() + 2;
+
+            let text = block.trim_matches('\n');
+            (!text.is_empty()).then_some(Section { first_line, text })
+        })
+    }
+
+    /// Parses every line on its own, pointing any error at the line it was found on.
+    pub fn parse_lines<T>(
+        &self,
+        parse_line: impl Fn(&str) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, AocError> {
+        Section {
+            first_line: 0,
+            text: &self.text,
+        }
+        .parse_lines(parse_line)
+    }
+
+    /// An error pointing at a cell of the [Grid].
+    pub fn cell_error(&self, x: usize, y: usize, message: impl Into<String>) -> AocError {
+        let text = self.lines().nth(y).unwrap_or_default();
+        let offset = text
+            .char_indices()
+            .nth(x)
+            .map_or(text.len(), |(offset, _)| offset);
+
+        ParseError::new(offset, message).at_line(y + 1, text)
+    }
+
+    pub fn grid(&self) -> Grid {
+        Grid {
+            rows: self.lines().map(|line| line.chars().collect()).collect(),
+        }
+    }
+}
+
+/// A block of lines from the input, which remembers where it starts so errors point at the right line.
+pub struct Section<'a> {
+    first_line: usize,
+    text: &'a str,
+}
+
+impl<'a> Section<'a> {
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> {
+        self.text.lines()
+    }
+
+    pub fn parse_lines<T>(
+        &self,
+        parse_line: impl Fn(&str) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, AocError> {
+        self.lines()
+            .enumerate()
+            .map(|(index, line)| {
+                parse_line(line).map_err(|error| error.at_line(self.first_line + index + 1, line))
+            })
+            .collect()
+    }
+}
+
+/// The input read as rows of characters, with `(0, 0)` being the top left.
+pub struct Grid {
+    rows: Vec<Vec<char>>,
+}
+
+impl Grid {
+    /// Every cell as `(x, y, character)`, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize, char)> {
+        self.rows.iter().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, character)| (x, y, *character))
+        })
+    }
+}
+
+#[test]
+fn test_normalizes_line_endings() {
+    let windows = PuzzleInput::new("3-5\r\n10-14\r\n\r\n1\r\n5\r\n");
+    let unix = PuzzleInput::new("3-5\n10-14\n\n1\n5\n\n");
+
+    assert_eq!(windows, unix);
+    assert_eq!(windows.text(), "3-5\n10-14\n\n1\n5");
+    assert_eq!(
+        windows.lines().collect::<Vec<_>>(),
+        vec!["3-5", "10-14", "", "1", "5"]
+    );
+    let sections: Vec<Vec<&str>> = windows
+        .sections()
+        .map(|section| section.lines().collect())
+        .collect();
+    assert_eq!(sections, vec![vec!["3-5", "10-14"], vec!["1", "5"]]);
+}
+
+#[test]
+fn test_errors_point_at_line_in_section() {
+    let input = PuzzleInput::new("1\n\n\n2\n3x\n");
+    let section = input.sections().nth(1).unwrap();
+
+    let error = section
+        .parse_lines(|line| {
+            line.parse::<u8>()
+                .map_err(|_| ParseError::new(1, "not a number"))
+        })
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        AocError::Parse { line: 5, column: 2, ref text, .. } if text == "3x"
+    ));
+}
+
+#[test]
+fn test_cell_error_points_at_character() {
+    let input = PuzzleInput::new("..\nö.x\n");
+
+    assert!(matches!(
+        input.cell_error(2, 1, "unexpected character 'x'"),
+        AocError::Parse {
+            line: 2,
+            column: 3,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_keeps_trailing_spaces() {
+    let input = PuzzleInput::new("123 \n  45\n*  +  \n");
+
+    assert_eq!(
+        input.lines().collect::<Vec<_>>(),
+        vec!["123 ", "  45", "*  +  "]
+    );
+}
+
+#[test]
+fn test_grid_cells() {
+    let grid = PuzzleInput::new(".S\r\n^.\r\n").grid();
+
+    assert_eq!(
+        grid.cells().collect::<Vec<_>>(),
+        vec![(0, 0, '.'), (1, 0, 'S'), (0, 1, '^'), (1, 1, '.')]
+    );
+}
@@ -0,0 +1,21 @@
+use crate::solution::Day;
+use crate::{day1, day2, day3, day4, day5, day6, day7, day8, day9, day10, day11};
+
+/// Every implemented day, in calendar order.
+pub static DAYS: &[&dyn Day] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+];
+
+pub fn find(number: u8) -> Option<&'static dyn Day> {
+    DAYS.iter().copied().find(|day| day.number() == number)
+}
@@ -0,0 +1,200 @@
+use crate::baseline::{Comparison, Verdict};
+use crate::memory::{MemoryUsage, format_bytes};
+use crate::runner::Measurement;
+use crate::solution::Phase;
+use crate::stopwatch::Statistics;
+use std::time::Duration;
+
+/// Prints one aligned row per measurement, followed by the total time spent. Parts that share a
+/// parsed input only show the parse time on the first of them. When allocations are counted, the
+/// peak, allocations and bytes allocated are shown for the parse and the solve in columns of their
+/// own, with the parse columns empty like the parse time.
+pub fn print_summary(measurements: &[Measurement]) {
+    let counted = measurements
+        .iter()
+        .any(|measurement| measurement.solve_memory.is_some());
+
+    let rows: Vec<Vec<String>> = measurements
+        .iter()
+        .map(|measurement| {
+            let mut row = vec![
+                measurement.day.to_string(),
+                measurement.part.to_string(),
+                measurement.input.clone(),
+                measurement
+                    .answer
+                    .clone()
+                    .unwrap_or_else(|| "TIMEOUT".to_owned()),
+                measurement
+                    .parse_duration
+                    .map(|duration| format!("{:?}", duration))
+                    .unwrap_or_default(),
+                format!("{:?}", measurement.solve_duration),
+            ];
+            if counted {
+                row.extend(memory_cells(measurement.parse_memory));
+                row.extend(memory_cells(measurement.solve_memory));
+            }
+            row
+        })
+        .collect();
+
+    let total_parse: Duration = measurements.iter().filter_map(|m| m.parse_duration).sum();
+    let total_solve: Duration = measurements.iter().map(|m| m.solve_duration).sum();
+    let mut footer = vec![
+        "Total".to_owned(),
+        String::new(),
+        String::new(),
+        format!("{:?}", total_parse + total_solve),
+        format!("{:?}", total_parse),
+        format!("{:?}", total_solve),
+    ];
+
+    let mut header = vec!["Day", "Part", "Input", "Answer", "Parse", "Solve"];
+    if counted {
+        header.extend([
+            "Parse peak",
+            "Parse allocations",
+            "Parse allocated",
+            "Solve peak",
+            "Solve allocations",
+            "Solve allocated",
+        ]);
+        footer.extend(vec![String::new(); 6]);
+    }
+
+    print_table(&header, &rows, Some(footer));
+}
+
+/// The peak, the number of allocations and the bytes allocated, empty when nothing was counted.
+fn memory_cells(usage: Option<MemoryUsage>) -> [String; 3] {
+    match usage {
+        Some(usage) => [
+            format_bytes(usage.peak_bytes),
+            usage.allocations.to_string(),
+            format_bytes(usage.total_bytes),
+        ],
+        None => Default::default(),
+    }
+}
+
+/// The statistics of benchmarking one part of a day against one input.
+pub struct BenchmarkRow {
+    pub day: u8,
+    pub phase: Phase,
+    pub input: String,
+    pub answer: String,
+    pub statistics: Statistics,
+}
+
+pub fn print_benchmarks(benchmarks: &[BenchmarkRow]) {
+    let rows: Vec<Vec<String>> = benchmarks
+        .iter()
+        .map(|benchmark| {
+            let statistics = &benchmark.statistics;
+            vec![
+                benchmark.day.to_string(),
+                benchmark.phase.to_string(),
+                benchmark.input.clone(),
+                benchmark.answer.clone(),
+                statistics.iterations.to_string(),
+                format!("{:?}", statistics.min),
+                format!("{:?}", statistics.median),
+                format!("{:?}", statistics.mean),
+                format!("{:?}", statistics.p95),
+                format!("{:?}", statistics.stddev),
+            ]
+        })
+        .collect();
+
+    print_table(
+        &[
+            "Day", "Phase", "Input", "Answer", "Runs", "Min", "Median", "Mean", "p95", "Stddev",
+        ],
+        &rows,
+        None,
+    );
+}
+
+pub fn print_comparisons(comparisons: &[Comparison]) {
+    let rows: Vec<Vec<String>> = comparisons
+        .iter()
+        .map(|comparison| {
+            let verdict = match comparison.verdict {
+                Verdict::Regressed => "REGRESSED",
+                Verdict::Improved => "IMPROVED",
+                Verdict::Unchanged => "OK",
+                Verdict::New => "NEW",
+            };
+
+            vec![
+                comparison.day.to_string(),
+                comparison.phase.to_string(),
+                comparison.input.clone(),
+                comparison
+                    .baseline
+                    .map(|baseline| format!("{:?}", baseline))
+                    .unwrap_or_default(),
+                format!("{:?}", comparison.current),
+                comparison
+                    .change
+                    .map(|change| format!("{change:+.1}%"))
+                    .unwrap_or_default(),
+                verdict.to_owned(),
+            ]
+        })
+        .collect();
+
+    print_table(
+        &[
+            "Day", "Phase", "Input", "Baseline", "Current", "Change", "Verdict",
+        ],
+        &rows,
+        None,
+    );
+}
+
+/// Prints the rows below the header with every column padded to its widest cell. The input column is
+/// left aligned, all other columns hold numbers and are right aligned.
+fn print_table(header: &[&str], rows: &[Vec<String>], footer: Option<Vec<String>>) {
+    let header: Vec<String> = header.iter().map(|title| title.to_string()).collect();
+
+    let mut widths: Vec<usize> = header.iter().map(String::len).collect();
+    for row in rows.iter().chain(footer.iter()) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let input_column = header.iter().position(|title| title == "Input");
+    let format_row = |row: &Vec<String>| {
+        row.iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, width))| {
+                if Some(column) == input_column {
+                    format!("{cell:<width$}")
+                } else {
+                    format!("{cell:>width$}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    };
+    let separator = widths
+        .iter()
+        .map(|width| "-".repeat(*width))
+        .collect::<Vec<_>>()
+        .join("-+-");
+
+    println!();
+    println!("{}", format_row(&header));
+    println!("{separator}");
+    for row in rows {
+        println!("{}", format_row(row));
+    }
+    if let Some(footer) = footer {
+        println!("{separator}");
+        println!("{}", format_row(&footer));
+    }
+}
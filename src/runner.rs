@@ -0,0 +1,401 @@
+use crate::cancel;
+use crate::cli::{InputSelection, RunOptions};
+use crate::error::AocError;
+use crate::input::{InputDirectory, InputKind, STDIN_PATH, read_input};
+use crate::log;
+use crate::memory::MemoryUsage;
+use crate::puzzle_input::PuzzleInput;
+use crate::report::BenchmarkRow;
+use crate::solution::{Day, Part, Phase};
+use crate::stopwatch::{BenchmarkOptions, Statistics, benchmark, time};
+use std::panic::resume_unwind;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+/// The outcome of running one part of a day against one input.
+pub struct Measurement {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    /// `None` when the part was cancelled because it ran past its timeout.
+    pub answer: Option<String>,
+    /// Time spent parsing the input. Only set for the first part run against the input, as the parts
+    /// after it reuse that parse.
+    pub parse_duration: Option<Duration>,
+    pub solve_duration: Duration,
+    /// What parsing allocated, only set next to `parse_duration` and when allocations are counted.
+    pub parse_memory: Option<MemoryUsage>,
+    pub solve_memory: Option<MemoryUsage>,
+}
+
+/// A file to run a day against, along with the parts that use it.
+pub struct Input {
+    pub name: String,
+    pub path: PathBuf,
+    pub parts: Vec<Part>,
+    /// Whether the file is an example, see [PuzzleInput::example].
+    pub example: bool,
+}
+
+impl Input {
+    fn puzzle_input(&self, data: &str) -> PuzzleInput {
+        if self.example {
+            PuzzleInput::example(data)
+        } else {
+            PuzzleInput::new(data)
+        }
+    }
+}
+
+/// The outcome of running a day against all of its inputs, along with what it logged meanwhile.
+pub struct DayRun {
+    pub day: u8,
+    /// The first error of any input, or the measurements of all of them.
+    pub result: Result<Vec<Measurement>, AocError>,
+    pub log: String,
+}
+
+/// Runs the days against their inputs with every input on a thread of its own. The runs are returned
+/// in the order of `days` and their log output is held back, so the output does not depend on which
+/// thread finishes first.
+pub fn run(days: &[&dyn Day], options: &RunOptions, directory: &InputDirectory) -> Vec<DayRun> {
+    let parts = selected_parts(options);
+
+    thread::scope(|scope| {
+        let days: Vec<_> = days
+            .iter()
+            .map(|day| {
+                let inputs = inputs(day.number(), &parts, &options.inputs, directory);
+                let threads: Vec<_> = inputs
+                    .into_iter()
+                    .map(|input| {
+                        scope.spawn(move || {
+                            log::capture(|| run_input(*day, &input, options.timeout))
+                        })
+                    })
+                    .collect();
+
+                (day.number(), threads)
+            })
+            .collect();
+
+        days.into_iter()
+            .map(|(day, threads)| {
+                let mut run = DayRun {
+                    day,
+                    result: Ok(Vec::new()),
+                    log: String::new(),
+                };
+
+                for thread in threads {
+                    let (result, log) = thread.join().unwrap_or_else(|panic| resume_unwind(panic));
+                    run.log += &log;
+
+                    match (&mut run.result, result) {
+                        (Ok(measurements), Ok(input_measurements)) => {
+                            measurements.extend(input_measurements)
+                        }
+                        (Ok(_), Err(error)) => run.result = Err(error),
+                        (Err(_), _) => {}
+                    }
+                }
+
+                run
+            })
+            .collect()
+    })
+}
+
+/// Parses the input once and then runs every part that uses it, timing each phase on its own. A part
+/// that runs past the timeout is cancelled and has no answer, the parts after it still run.
+pub fn run_input(
+    day: &dyn Day,
+    input: &Input,
+    timeout: Option<Duration>,
+) -> Result<Vec<Measurement>, AocError> {
+    let number = day.number();
+    let data = read_input(&input.path)?;
+
+    let (parsed, parse_duration, parse_memory) =
+        time(&format!("Day {number}, Parsing {}", input.name), || {
+            day.parse(&input.puzzle_input(&data))
+        });
+    let parsed = parsed.map_err(failed(number, Phase::Parse, input))?;
+
+    input
+        .parts
+        .iter()
+        .enumerate()
+        .map(|(index, part)| {
+            let (answer, solve_duration, solve_memory) =
+                time(&format!("Day {number}, Part {part} {}", input.name), || {
+                    cancel::with_timeout(timeout, || day.solve(*part, parsed.as_ref()))
+                });
+            let answer = match answer {
+                Ok(answer) => Some(answer),
+                Err(AocError::Cancelled) => None,
+                Err(error) => return Err(failed(number, Phase::Solve(*part), input)(error)),
+            };
+
+            Ok(Measurement {
+                day: number,
+                part: *part,
+                input: input.name.clone(),
+                answer,
+                parse_duration: (index == 0).then_some(parse_duration),
+                solve_duration,
+                parse_memory: parse_memory.filter(|_| index == 0),
+                solve_memory,
+            })
+        })
+        .collect()
+}
+
+/// Benchmarks every phase of the day against its inputs. A part that runs past the timeout is
+/// reported as TIMEOUT and the parts after it are still measured.
+pub fn bench(
+    day: &dyn Day,
+    options: &RunOptions,
+    benchmark_options: &BenchmarkOptions,
+    directory: &InputDirectory,
+) -> Result<Vec<BenchmarkRow>, AocError> {
+    let number = day.number();
+    let mut rows = Vec::new();
+
+    for input in inputs(number, &selected_parts(options), &options.inputs, directory) {
+        let data = read_input(&input.path)?;
+
+        let parse_benchmark = benchmark(
+            &format!("Day {number}, Parsing {}", input.name),
+            benchmark_options,
+            || day.parse(&input.puzzle_input(&data)),
+        );
+        let parsed = parse_benchmark
+            .result
+            .map_err(failed(number, Phase::Parse, &input))?;
+        rows.push(BenchmarkRow {
+            day: number,
+            phase: Phase::Parse,
+            input: input.name.clone(),
+            answer: String::new(),
+            statistics: Statistics::from_samples(&parse_benchmark.samples),
+        });
+
+        for part in &input.parts {
+            let name = format!("Day {number}, Part {part} {}", input.name);
+            let solve =
+                || cancel::with_timeout(options.timeout, || day.solve(*part, parsed.as_ref()));
+
+            // A part that runs past the timeout once would do so on every run, so it is tried once
+            // before it is measured, and reported as TIMEOUT like `run` does.
+            if options.timeout.is_some() {
+                let (answer, duration, _) = time(&name, solve);
+                if let Err(AocError::Cancelled) = answer {
+                    rows.push(BenchmarkRow {
+                        day: number,
+                        phase: Phase::Solve(*part),
+                        input: input.name.clone(),
+                        answer: "TIMEOUT".to_owned(),
+                        statistics: Statistics::from_samples(&[duration]),
+                    });
+                    continue;
+                }
+            }
+
+            let benchmark = benchmark(&name, benchmark_options, solve);
+            let answer = match benchmark.result {
+                Ok(answer) => answer,
+                Err(AocError::Cancelled) => "TIMEOUT".to_owned(),
+                Err(error) => return Err(failed(number, Phase::Solve(*part), &input)(error)),
+            };
+
+            rows.push(BenchmarkRow {
+                day: number,
+                phase: Phase::Solve(*part),
+                input: input.name.clone(),
+                answer,
+                statistics: Statistics::from_samples(&benchmark.samples),
+            });
+        }
+    }
+
+    Ok(rows)
+}
+
+fn failed(day: u8, phase: Phase, input: &Input) -> impl FnOnce(AocError) -> AocError {
+    let path = input.path.clone();
+
+    move |error| AocError::Failed {
+        day,
+        phase,
+        path,
+        error: Box::new(error),
+    }
+}
+
+/// The part given on the command line, or both.
+pub fn selected_parts(options: &RunOptions) -> Vec<Part> {
+    match options.part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    }
+}
+
+fn input_name(path: &Path) -> String {
+    if path == Path::new(STDIN_PATH) {
+        "stdin".to_owned()
+    } else {
+        path.display().to_string()
+    }
+}
+
+/// Lists the inputs to run the parts against. Parts that read the same file are grouped, so that file
+/// only needs to be parsed once.
+pub fn inputs(
+    day: u8,
+    parts: &[Part],
+    selection: &InputSelection,
+    directory: &InputDirectory,
+) -> Vec<Input> {
+    let mut inputs: Vec<Input> = Vec::new();
+
+    for part in parts {
+        let example = (
+            "Example".to_owned(),
+            directory.path(day, InputKind::Example(*part)),
+            true,
+        );
+        let puzzle = (
+            "Puzzle".to_owned(),
+            directory.path(day, InputKind::Puzzle),
+            false,
+        );
+        let files = |paths: &[PathBuf], example: bool| {
+            paths
+                .iter()
+                .map(|path| (input_name(path), path.clone(), example))
+                .collect()
+        };
+
+        let paths = match selection {
+            InputSelection::ExampleAndPuzzle => vec![example, puzzle],
+            InputSelection::ExampleOnly => vec![example],
+            InputSelection::PuzzleOnly => vec![puzzle],
+            InputSelection::Files(paths) => files(paths, false),
+            InputSelection::ExampleFiles(paths) => files(paths, true),
+        };
+
+        for (name, path, example) in paths {
+            match inputs.iter_mut().find(|input| input.path == path) {
+                Some(input) => input.parts.push(*part),
+                None => inputs.push(Input {
+                    name,
+                    path,
+                    parts: vec![*part],
+                    example,
+                }),
+            }
+        }
+    }
+
+    inputs
+}
+
+#[test]
+fn test_inputs_from_files() {
+    let selection = InputSelection::Files(vec![PathBuf::from("a.txt"), PathBuf::from("-")]);
+
+    let inputs = inputs(1, &Part::ALL, &selection, &InputDirectory::default());
+    let names: Vec<(&str, &[Part])> = inputs
+        .iter()
+        .map(|input| (input.name.as_str(), input.parts.as_slice()))
+        .collect();
+
+    assert_eq!(
+        names,
+        [("a.txt", &Part::ALL[..]), ("stdin", &Part::ALL[..])]
+    );
+    assert!(inputs.iter().all(|input| !input.example));
+}
+
+#[test]
+fn test_example_inputs() {
+    let directory = InputDirectory::default();
+    let examples = |selection| {
+        inputs(8, &[Part::One], &selection, &directory)
+            .iter()
+            .map(|input| input.example)
+            .collect::<Vec<bool>>()
+    };
+
+    assert_eq!(examples(InputSelection::ExampleAndPuzzle), [true, false]);
+    assert_eq!(
+        examples(InputSelection::ExampleFiles(vec![PathBuf::from("a.txt")])),
+        [true]
+    );
+}
+
+/// Part 1 never finishes on its own, part 2 answers right away.
+#[cfg(test)]
+struct Stuck;
+
+#[cfg(test)]
+impl Day for Stuck {
+    fn number(&self) -> u8 {
+        1
+    }
+
+    fn parse(&self, _input: &PuzzleInput) -> Result<Box<dyn std::any::Any>, AocError> {
+        Ok(Box::new(()))
+    }
+
+    fn solve(&self, part: Part, _input: &dyn std::any::Any) -> Result<String, AocError> {
+        match part {
+            Part::One => loop {
+                cancel::check()?;
+            },
+            Part::Two => Ok("2".to_owned()),
+        }
+    }
+}
+
+#[test]
+fn test_bench_reports_timeouts_and_keeps_going() {
+    let path = std::env::temp_dir().join(format!("aoc-2025-bench-{}", std::process::id()));
+    std::fs::write(&path, "").unwrap();
+    let options = RunOptions {
+        days: crate::cli::DaySelection::Single(1),
+        part: None,
+        inputs: InputSelection::Files(vec![path.clone()]),
+        timeout: Some(Duration::from_millis(10)),
+    };
+    let benchmark_options = BenchmarkOptions {
+        warm_up_iterations: 0,
+        budget: Duration::ZERO,
+        min_iterations: 1,
+        max_iterations: 1,
+    };
+
+    let rows = bench(
+        &Stuck,
+        &options,
+        &benchmark_options,
+        &InputDirectory::default(),
+    );
+    std::fs::remove_file(&path).unwrap();
+
+    let answers: Vec<(Phase, String)> = rows
+        .unwrap()
+        .into_iter()
+        .map(|row| (row.phase, row.answer))
+        .collect();
+    assert_eq!(
+        answers,
+        [
+            (Phase::Parse, String::new()),
+            (Phase::Solve(Part::One), "TIMEOUT".to_owned()),
+            (Phase::Solve(Part::Two), "2".to_owned()),
+        ]
+    );
+}
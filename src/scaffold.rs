@@ -0,0 +1,301 @@
+use crate::input::{InputDirectory, InputKind};
+use crate::solution::Part;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The module of a new day, with `{day}` standing in for its number.
+const TEMPLATE: &str = r#"use crate::error::{AocError, ParseError};
+#[cfg(test)]
+use crate::input::{InputDirectory, InputKind};
+use crate::puzzle_input::PuzzleInput;
+#[cfg(test)]
+use crate::solution::Part;
+use crate::solution::Solution;
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    const DAY: u8 = {day};
+
+    type Input = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &PuzzleInput) -> Result<Vec<String>, AocError> {
+        input.parse_lines(parse_line)
+    }
+
+    fn part_1(&self, _lines: &Vec<String>) -> Result<u64, AocError> {
+        Err(AocError::Unsolvable(
+            "part 1 has not been solved yet".to_owned(),
+        ))
+    }
+
+    fn part_2(&self, _lines: &Vec<String>) -> Result<u64, AocError> {
+        Err(AocError::Unsolvable(
+            "part 2 has not been solved yet".to_owned(),
+        ))
+    }
+}
+
+fn parse_line(line: &str) -> Result<String, ParseError> {
+    Ok(line.to_owned())
+}
+
+#[test]
+#[ignore = "day {day} has not been solved yet"]
+fn test_run_example_part_1() {
+    let example_data = InputDirectory::default()
+        .read({day}, InputKind::Example(Part::One))
+        .unwrap();
+    assert_eq!(
+        Day{day}.part_1(&Day{day}.parse(&example_data).unwrap()).unwrap(),
+        0
+    );
+}
+
+#[test]
+#[ignore = "day {day} has not been solved yet"]
+fn test_run_example_part_2() {
+    let example_data = InputDirectory::default()
+        .read({day}, InputKind::Example(Part::Two))
+        .unwrap();
+    assert_eq!(
+        Day{day}.part_2(&Day{day}.parse(&example_data).unwrap()).unwrap(),
+        0
+    );
+}
+"#;
+
+/// Generates `src/dayN.rs`, declares and registers it, and creates empty inputs for it, so the day
+/// can be run right away. Either every file is written or none are. Returns the files that were
+/// written.
+pub fn new_day(
+    day: u8,
+    source_dir: &Path,
+    directory: &InputDirectory,
+) -> Result<Vec<PathBuf>, String> {
+    let module_path = source_dir.join(format!("day{day}.rs"));
+    if module_path.exists() {
+        return Err(format!("{} already exists", module_path.display()));
+    }
+
+    let lib_path = source_dir.join("lib.rs");
+    let registry_path = source_dir.join("registry.rs");
+    let lib = declare_module(&read(&lib_path)?, day)?;
+    let registry = register_day(&read(&registry_path)?, day)?;
+
+    let mut files = vec![
+        (module_path, TEMPLATE.replace("{day}", &day.to_string())),
+        (lib_path, lib),
+        (registry_path, registry),
+    ];
+    for kind in [InputKind::Example(Part::One), InputKind::Puzzle] {
+        let path = directory.path(day, kind);
+        if !path.exists() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+            }
+            files.push((path, String::new()));
+        }
+    }
+
+    write_all(files)
+}
+
+/// Writes every file to a temporary file next to it first, and only then moves them into place. When
+/// a file cannot be moved, the files moved before it are put back the way they were.
+fn write_all(files: Vec<(PathBuf, String)>) -> Result<Vec<PathBuf>, String> {
+    let mut temporaries: Vec<PathBuf> = Vec::new();
+    for (path, contents) in &files {
+        let temporary = temporary_path(path);
+        if let Err(message) = write(&temporary, contents) {
+            remove_all(&temporaries);
+            return Err(message);
+        }
+        temporaries.push(temporary);
+    }
+
+    let mut replaced: Vec<(&Path, Option<String>)> = Vec::new();
+    for ((path, _), temporary) in files.iter().zip(&temporaries) {
+        let before = fs::read_to_string(path).ok();
+        if let Err(err) = fs::rename(temporary, path) {
+            for (path, before) in replaced.iter().rev() {
+                let _ = match before {
+                    Some(contents) => fs::write(path, contents),
+                    None => fs::remove_file(path),
+                };
+            }
+            remove_all(&temporaries);
+            return Err(format!("Failed to write {}: {err}", path.display()));
+        }
+        replaced.push((path, before));
+    }
+
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+fn temporary_path(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+    path.with_file_name(format!(".{name}.new"))
+}
+
+/// Removes the files that exist, ignoring the ones that were already moved away.
+fn remove_all(paths: &[PathBuf]) {
+    for path in paths {
+        let _ = fs::remove_file(path);
+    }
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| format!("Failed to read {}: {err}", path.display()))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|err| format!("Failed to write {}: {err}", path.display()))
+}
+
+/// Adds `pub mod dayN;` to the module declarations of `lib.rs`, keeping them sorted by name.
+fn declare_module(lib: &str, day: u8) -> Result<String, String> {
+    let module = format!("day{day}");
+    let declaration = format!("pub mod {module};");
+    let mut lines: Vec<&str> = lib.lines().collect();
+
+    let day_modules: Vec<usize> = (0..lines.len())
+        .filter(|index| lines[*index].starts_with("pub mod day"))
+        .collect();
+    let Some(last) = day_modules.last() else {
+        return Err("Could not find the day modules in lib.rs".to_owned());
+    };
+
+    let position = day_modules
+        .iter()
+        .copied()
+        .find(|index| {
+            lines[*index]
+                .trim_start_matches("pub mod ")
+                .trim_end_matches(';')
+                > module.as_str()
+        })
+        .unwrap_or(last + 1);
+    lines.insert(position, &declaration);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds the day to the imports and the `DAYS` list of `registry.rs`, keeping the calendar order.
+fn register_day(registry: &str, day: u8) -> Result<String, String> {
+    let module = format!("day{day}");
+    let imports_start = registry
+        .find("use crate::{")
+        .ok_or("Could not find the day imports in registry.rs")?
+        + "use crate::{".len();
+    let imports_end = imports_start
+        + registry[imports_start..]
+            .find('}')
+            .ok_or("Could not find the end of the day imports in registry.rs")?;
+
+    let mut imports: Vec<&str> = registry[imports_start..imports_end]
+        .split(',')
+        .map(str::trim)
+        .filter(|import| !import.is_empty())
+        .collect();
+    imports.push(&module);
+    imports.sort_by_key(|import| import.trim_start_matches("day").parse::<u8>().unwrap_or(0));
+
+    let mut lines: Vec<String> = registry[imports_end..].lines().map(str::to_owned).collect();
+    let days_end = lines
+        .iter()
+        .position(|line| line.trim() == "];")
+        .ok_or("Could not find the end of DAYS in registry.rs")?;
+    let position = lines[..days_end]
+        .iter()
+        .position(|line| {
+            line.trim()
+                .strip_prefix("&day")
+                .and_then(|entry| entry.split("::").next())
+                .and_then(|number| number.parse::<u8>().ok())
+                .is_some_and(|number| number > day)
+        })
+        .unwrap_or(days_end);
+    lines.insert(position, format!("    &{module}::Day{day},"));
+
+    Ok(format!(
+        "{}{}{}\n",
+        &registry[..imports_start],
+        imports.join(", "),
+        lines.join("\n")
+    ))
+}
+
+#[test]
+fn test_declare_module() {
+    let lib = "pub mod answers;\npub mod day1;\npub mod day10;\npub mod day2;\npub mod error;\n";
+
+    assert_eq!(
+        declare_module(lib, 12).unwrap(),
+        "pub mod answers;\npub mod day1;\npub mod day10;\npub mod day12;\npub mod day2;\npub mod error;\n"
+    );
+    assert_eq!(
+        declare_module(lib, 3).unwrap(),
+        "pub mod answers;\npub mod day1;\npub mod day10;\npub mod day2;\npub mod day3;\npub mod error;\n"
+    );
+    assert!(declare_module("pub mod error;\n", 3).is_err());
+}
+
+#[test]
+fn test_register_day() {
+    let registry = "use crate::{day1, day2, day10};\n\npub static DAYS: &[&dyn Day] = &[\n    &day1::Day1,\n    &day2::Day2,\n    &day10::Day10,\n];\n";
+
+    assert_eq!(
+        register_day(registry, 3).unwrap(),
+        "use crate::{day1, day2, day3, day10};\n\npub static DAYS: &[&dyn Day] = &[\n    &day1::Day1,\n    &day2::Day2,\n    &day3::Day3,\n    &day10::Day10,\n];\n"
+    );
+    assert!(
+        register_day(registry, 12)
+            .unwrap()
+            .contains("day10, day12};")
+    );
+    assert!(
+        register_day(registry, 12)
+            .unwrap()
+            .contains("    &day10::Day10,\n    &day12::Day12,\n];")
+    );
+}
+
+#[test]
+fn test_write_all_leaves_nothing_behind_on_failure() {
+    let dir = std::env::temp_dir().join(format!("aoc-2025-scaffold-{}", std::process::id()));
+    fs::create_dir_all(dir.join("blocked/inside")).unwrap();
+    fs::write(dir.join("lib.rs"), "before").unwrap();
+
+    // A file cannot replace a directory that has files in it.
+    let result = write_all(vec![
+        (dir.join("day12.rs"), "module".to_owned()),
+        (dir.join("lib.rs"), "after".to_owned()),
+        (dir.join("blocked"), String::new()),
+    ]);
+
+    let mut left: Vec<String> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    left.sort();
+    let lib = fs::read_to_string(dir.join("lib.rs")).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(result.is_err());
+    assert_eq!(left, ["blocked", "lib.rs"]);
+    assert_eq!(lib, "before");
+}
+
+#[test]
+fn test_template_is_filled_in() {
+    let module = TEMPLATE.replace("{day}", "12");
+
+    assert!(module.contains("pub struct Day12;"));
+    assert!(module.contains("const DAY: u8 = 12;"));
+    assert!(module.contains(".read(12, InputKind::Example(Part::One))"));
+}
@@ -0,0 +1,89 @@
+use std::cell::RefCell;
+use std::thread::LocalKey;
+
+/// A thread local that holds a value only while a call runs, like the frames being recorded or the
+/// deadline of a part. Declared with `thread_local!` as `const { RefCell::new(None) }`.
+pub type Slot<T> = RefCell<Option<T>>;
+
+/// Runs `function` with `value` in the slot of this thread, and returns what the slot holds once it
+/// is done next to its result. The value the slot held before is put back afterwards, also when
+/// `function` panics, so calls can be nested.
+pub fn with<T: 'static, R>(
+    slot: &'static LocalKey<Slot<T>>,
+    value: Option<T>,
+    function: impl FnOnce() -> R,
+) -> (R, Option<T>) {
+    let mut restore = Restore {
+        slot,
+        outer: Some(slot.replace(value)),
+    };
+    let result = function();
+    let inner = slot.replace(restore.outer.take().flatten());
+
+    (result, inner)
+}
+
+/// Calls `function` with the value in the slot of this thread, or returns `None` when no [with] put
+/// one there.
+pub fn get<T: 'static, R>(
+    slot: &'static LocalKey<Slot<T>>,
+    function: impl FnOnce(&mut T) -> R,
+) -> Option<R> {
+    slot.with_borrow_mut(|value| value.as_mut().map(function))
+}
+
+pub fn is_set<T: 'static>(slot: &'static LocalKey<Slot<T>>) -> bool {
+    slot.with_borrow(Option::is_some)
+}
+
+/// Puts the outer value back when `function` unwinds out of [with].
+struct Restore<T: 'static> {
+    slot: &'static LocalKey<Slot<T>>,
+    outer: Option<Option<T>>,
+}
+
+impl<T: 'static> Drop for Restore<T> {
+    fn drop(&mut self) {
+        if let Some(outer) = self.outer.take() {
+            // Fails while the thread is being torn down, when nothing reads the slot anymore.
+            let _ = self.slot.try_with(|slot| slot.replace(outer));
+        }
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    static NUMBERS: Slot<Vec<u32>> = const { RefCell::new(None) };
+}
+
+#[test]
+fn test_nested_scopes() {
+    let before = get(&NUMBERS, |numbers| numbers.push(0));
+
+    let ((inner, set), outer) = with(&NUMBERS, Some(Vec::new()), || {
+        get(&NUMBERS, |numbers| numbers.push(1));
+        let (_, inner) = with(&NUMBERS, Some(Vec::new()), || {
+            get(&NUMBERS, |numbers| numbers.push(2));
+        });
+        get(&NUMBERS, |numbers| numbers.push(3));
+        (inner, is_set(&NUMBERS))
+    });
+
+    assert_eq!(before, None);
+    assert_eq!(inner, Some(vec![2]));
+    assert!(set);
+    assert_eq!(outer, Some(vec![1, 3]));
+    assert!(!is_set(&NUMBERS));
+}
+
+#[test]
+fn test_scope_ends_on_panic() {
+    let panicked = std::panic::catch_unwind(|| {
+        with(&NUMBERS, Some(Vec::new()), || {
+            panic!("the scope ends anyway")
+        })
+    });
+
+    assert!(panicked.is_err());
+    assert!(!is_set(&NUMBERS));
+}
@@ -0,0 +1,97 @@
+use crate::error::AocError;
+use crate::puzzle_input::PuzzleInput;
+use std::any::Any;
+use std::fmt::{Display, Formatter};
+
+/// A single day of the advent calendar.
+///
+/// Parsing turns the raw puzzle text into `Self::Input`, which is then handed to either part.
+pub trait Solution {
+    const DAY: u8;
+
+    type Input: 'static;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(&self, input: &PuzzleInput) -> Result<Self::Input, AocError>;
+
+    fn part_1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError>;
+
+    fn part_2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError>;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// A step of running a day that is timed on its own.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => f.write_str("parse"),
+            Phase::Solve(part) => write!(f, "part-{part}"),
+        }
+    }
+}
+
+/// Object safe view on a [Solution], so days with different input and answer types can live in one registry.
+///
+/// The parsed input is passed around as [Any], so it can be parsed once and then shared by both parts.
+pub trait Day: Sync {
+    fn number(&self) -> u8;
+
+    fn parse(&self, input: &PuzzleInput) -> Result<Box<dyn Any>, AocError>;
+
+    /// Solves a part for input returned by [Day::parse] of the same day.
+    fn solve(&self, part: Part, input: &dyn Any) -> Result<String, AocError>;
+}
+
+impl<T: Solution + Sync> Day for T {
+    fn number(&self) -> u8 {
+        T::DAY
+    }
+
+    fn parse(&self, input: &PuzzleInput) -> Result<Box<dyn Any>, AocError> {
+        Ok(Box::new(Solution::parse(self, input)?))
+    }
+
+    fn solve(&self, part: Part, input: &dyn Any) -> Result<String, AocError> {
+        let input = input.downcast_ref::<T::Input>().unwrap_or_else(|| {
+            panic!(
+                "Day {} was given input that was not parsed by day {}",
+                T::DAY,
+                T::DAY
+            )
+        });
+
+        match part {
+            Part::One => self.part_1(input).map(|answer| answer.to_string()),
+            Part::Two => self.part_2(input).map(|answer| answer.to_string()),
+        }
+    }
+}
@@ -1,9 +1,174 @@
-use std::time::SystemTime;
-
-pub fn time(name: &str, function_to_time: impl Fn()) {
-    let time = SystemTime::now();
-    function_to_time();
-    time.elapsed()
-        .iter()
-        .for_each(|x| println!("{} took {:?} to run", name, x));
+use crate::log::info;
+use crate::memory::{self, MemoryUsage};
+use std::time::{Duration, Instant};
+
+/// Runs `function_to_time` once, logging and returning how long it took alongside its result, and
+/// what it allocated when allocations are counted.
+pub fn time<T>(name: &str, function_to_time: impl Fn() -> T) -> (T, Duration, Option<MemoryUsage>) {
+    let time = Instant::now();
+    let (result, memory) = memory::measure(function_to_time);
+    let elapsed = time.elapsed();
+
+    match memory {
+        Some(memory) => info!("{} took {:?} to run, {}", name, elapsed, memory),
+        None => info!("{} took {:?} to run", name, elapsed),
+    }
+
+    (result, elapsed, memory)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkOptions {
+    /// Runs that are done before measuring, so caches and the allocator are warmed up.
+    pub warm_up_iterations: u32,
+    /// Measuring stops once this much time was spent and at least `min_iterations` were done.
+    pub budget: Duration,
+    pub min_iterations: u32,
+    pub max_iterations: u32,
+}
+
+impl Default for BenchmarkOptions {
+    fn default() -> Self {
+        BenchmarkOptions {
+            warm_up_iterations: 3,
+            budget: Duration::from_secs(1),
+            min_iterations: 5,
+            max_iterations: 10_000,
+        }
+    }
+}
+
+pub struct Benchmark<T> {
+    /// The result of the last run.
+    pub result: T,
+    pub samples: Vec<Duration>,
+}
+
+/// Runs `function_to_benchmark` repeatedly according to the options and records every run.
+pub fn benchmark<T>(
+    name: &str,
+    options: &BenchmarkOptions,
+    function_to_benchmark: impl Fn() -> T,
+) -> Benchmark<T> {
+    for _ in 0..options.warm_up_iterations {
+        function_to_benchmark();
+    }
+
+    let started = Instant::now();
+    let mut samples: Vec<Duration> = Vec::new();
+    let mut result;
+
+    loop {
+        let time = Instant::now();
+        result = function_to_benchmark();
+        samples.push(time.elapsed());
+
+        let iterations = samples.len() as u32;
+        let budget_spent = started.elapsed() >= options.budget;
+        if iterations >= options.max_iterations
+            || (budget_spent && iterations >= options.min_iterations)
+        {
+            break;
+        }
+    }
+
+    let statistics = Statistics::from_samples(&samples);
+    info!(
+        "{} took {:?} (median) over {} runs",
+        name, statistics.median, statistics.iterations
+    );
+
+    Benchmark { result, samples }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statistics {
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl Statistics {
+    pub fn from_samples(samples: &[Duration]) -> Statistics {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let iterations = sorted.len();
+        if iterations == 0 {
+            return Statistics {
+                iterations,
+                min: Duration::ZERO,
+                median: Duration::ZERO,
+                mean: Duration::ZERO,
+                p95: Duration::ZERO,
+                stddev: Duration::ZERO,
+            };
+        }
+
+        let median = if iterations.is_multiple_of(2) {
+            (sorted[iterations / 2 - 1] + sorted[iterations / 2]) / 2
+        } else {
+            sorted[iterations / 2]
+        };
+
+        // Nearest-rank percentile, so the p95 is always one of the measured samples.
+        let p95_rank = (iterations as f64 * 0.95).ceil() as usize;
+
+        let mean_seconds =
+            sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / iterations as f64;
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean_seconds).powi(2))
+            .sum::<f64>()
+            / iterations as f64;
+
+        Statistics {
+            iterations,
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean_seconds),
+            p95: sorted[p95_rank.max(1) - 1],
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+#[test]
+fn test_statistics_from_samples() {
+    let samples: Vec<Duration> = [5, 1, 4, 2, 3].map(Duration::from_millis).to_vec();
+    let statistics = Statistics::from_samples(&samples);
+
+    assert_eq!(statistics.iterations, 5);
+    assert_eq!(statistics.min, Duration::from_millis(1));
+    assert_eq!(statistics.median, Duration::from_millis(3));
+    assert_eq!(statistics.mean, Duration::from_millis(3));
+    assert_eq!(statistics.p95, Duration::from_millis(5));
+    assert_eq!(statistics.stddev.as_micros(), 1414);
+}
+
+#[test]
+fn test_statistics_of_even_amount_of_samples() {
+    let samples: Vec<Duration> = [4, 1, 3, 2].map(Duration::from_millis).to_vec();
+    let statistics = Statistics::from_samples(&samples);
+
+    assert_eq!(statistics.median, Duration::from_micros(2500));
+    assert_eq!(statistics.p95, Duration::from_millis(4));
+}
+
+#[test]
+fn test_benchmark_respects_iteration_limits() {
+    let options = BenchmarkOptions {
+        warm_up_iterations: 2,
+        budget: Duration::ZERO,
+        min_iterations: 3,
+        max_iterations: 10,
+    };
+
+    let benchmark = benchmark("test", &options, || 42);
+
+    assert_eq!(benchmark.result, 42);
+    assert_eq!(benchmark.samples.len(), 3);
 }
@@ -0,0 +1,479 @@
+use crate::fetch::{Session, YEAR};
+use crate::http::{Request, send};
+use crate::solution::Part;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// What the server made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// The answer was sent before the wait after the previous one was over, and was not checked.
+    TooSoon,
+    /// The part was already solved, so the answer was not checked.
+    AlreadySolved,
+}
+
+impl Verdict {
+    const ALL: [Verdict; 6] = [
+        Verdict::Correct,
+        Verdict::TooHigh,
+        Verdict::TooLow,
+        Verdict::Wrong,
+        Verdict::TooSoon,
+        Verdict::AlreadySolved,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wrong => "wrong",
+            Verdict::TooSoon => "too-soon",
+            Verdict::AlreadySolved => "already-solved",
+        }
+    }
+
+    fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "That's the right answer",
+            Verdict::TooHigh => "That's not the right answer, it is too high",
+            Verdict::TooLow => "That's not the right answer, it is too low",
+            Verdict::Wrong => "That's not the right answer",
+            Verdict::TooSoon => "An answer was given too recently",
+            Verdict::AlreadySolved => "This part is already solved",
+        })
+    }
+}
+
+/// A parsed answer page: the verdict, and how long to wait before the next answer may be sent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    pub verdict: Verdict,
+    pub wait: Duration,
+}
+
+/// A submitted answer, as kept in the guess history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Guess {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch at which the answer was sent.
+    pub sent_at: u64,
+    pub wait: Duration,
+}
+
+/// Every answer sent so far, stored as lines of `<day> <part> <verdict> <sent at> <wait> <answer>`.
+pub struct History {
+    path: PathBuf,
+    guesses: Vec<Guess>,
+}
+
+impl History {
+    /// Reads the history, which starts out empty when the file does not exist yet.
+    pub fn read(path: &Path) -> Result<History, String> {
+        let guesses = match fs::read_to_string(path) {
+            Ok(text) => parse_history(&text).map_err(|err| format!("{}: {err}", path.display()))?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(format!("Failed to read {}: {err}", path.display())),
+        };
+
+        Ok(History {
+            path: path.to_path_buf(),
+            guesses,
+        })
+    }
+
+    /// Explains why the answer should not be sent, when earlier guesses already tell it is wrong.
+    pub fn check(&self, day: u8, part: Part, answer: &str) -> Result<(), String> {
+        let guesses: Vec<&Guess> = self
+            .guesses
+            .iter()
+            .filter(|guess| guess.day == day && guess.part == part)
+            .collect();
+
+        if let Some(correct) = guesses
+            .iter()
+            .find(|guess| guess.verdict == Verdict::Correct)
+        {
+            return Err(format!(
+                "Day {day} part {part} was already solved with {}",
+                correct.answer
+            ));
+        }
+        if let Some(guess) = guesses
+            .iter()
+            .find(|guess| guess.answer == answer && guess.verdict.is_wrong())
+        {
+            return Err(format!("{answer} was already tried: {}", guess.verdict));
+        }
+
+        let Ok(number) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+        let numbers_with = |verdict: Verdict| {
+            guesses
+                .iter()
+                .filter(move |guess| guess.verdict == verdict)
+                .filter_map(|guess| guess.answer.parse::<i128>().ok())
+        };
+        if let Some(high) = numbers_with(Verdict::TooHigh)
+            .min()
+            .filter(|high| number >= *high)
+        {
+            return Err(format!("{answer} is not below {high}, which was too high"));
+        }
+        if let Some(low) = numbers_with(Verdict::TooLow)
+            .max()
+            .filter(|low| number <= *low)
+        {
+            return Err(format!("{answer} is not above {low}, which was too low"));
+        }
+
+        Ok(())
+    }
+
+    /// How long is left of the wait the server asked for after the last answers.
+    pub fn wait_left(&self, now: u64) -> Option<Duration> {
+        self.guesses
+            .iter()
+            .map(|guess| guess.sent_at + guess.wait.as_secs())
+            .max()
+            .filter(|until| *until > now)
+            .map(|until| Duration::from_secs(until - now))
+    }
+
+    pub fn record(&mut self, guess: Guess) -> Result<(), String> {
+        let new_file = !self.path.exists();
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|err| format!("Failed to open {}: {err}", self.path.display()))?;
+
+        let mut line = String::new();
+        if new_file {
+            line += "# Every answer sent by `aoc-2025 submit`.\n";
+            line += "# <day> <part> <verdict> <sent at, seconds since 1970> <wait in seconds> <answer>\n";
+        }
+        line += &format!(
+            "{} {} {} {} {} {}\n",
+            guess.day,
+            guess.part,
+            guess.verdict.name(),
+            guess.sent_at,
+            guess.wait.as_secs(),
+            guess.answer
+        );
+        file.write_all(line.as_bytes())
+            .map_err(|err| format!("Failed to write {}: {err}", self.path.display()))?;
+
+        self.guesses.push(guess);
+        Ok(())
+    }
+}
+
+fn parse_history(text: &str) -> Result<Vec<Guess>, String> {
+    let mut guesses = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line_number = index + 1;
+        let invalid = || format!("line {line_number}: invalid guess '{line}'");
+        let fields: Vec<&str> = line.splitn(6, ' ').collect();
+        let [day, part, verdict, sent_at, wait, answer] = fields[..] else {
+            return Err(invalid());
+        };
+
+        guesses.push(Guess {
+            day: day.parse().map_err(|_| invalid())?,
+            part: match part {
+                "1" => Part::One,
+                "2" => Part::Two,
+                _ => return Err(invalid()),
+            },
+            answer: answer.to_owned(),
+            verdict: Verdict::ALL
+                .into_iter()
+                .find(|candidate| candidate.name() == verdict)
+                .ok_or_else(invalid)?,
+            sent_at: sent_at.parse().map_err(|_| invalid())?,
+            wait: Duration::from_secs(wait.parse().map_err(|_| invalid())?),
+        });
+    }
+
+    Ok(guesses)
+}
+
+/// Reads the verdict from the answer page. Only the text matters, the markup around it is ignored.
+pub fn parse_outcome(page: &str) -> Result<Outcome, String> {
+    let text = strip_tags(page);
+
+    let verdict = if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::TooSoon
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if text.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else {
+        return Err(format!("Unrecognized answer page: {}", text.trim()));
+    };
+
+    Ok(Outcome {
+        verdict,
+        wait: parse_wait(&text).unwrap_or_default(),
+    })
+}
+
+/// Finds `You have 1m 30s left to wait` or `Please wait 5 minutes before trying again`.
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some(start) = text.find("You have ") {
+        let left = &text[start + "You have ".len()..];
+        let left = &left[..left.find(" left to wait")?];
+
+        let mut seconds = 0;
+        for amount in left.split_whitespace() {
+            seconds += match amount.split_at(amount.len() - 1) {
+                (minutes, "m") => minutes.parse::<u64>().ok()? * 60,
+                (secs, "s") => secs.parse::<u64>().ok()?,
+                _ => return None,
+            };
+        }
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let start = text.find("wait ")? + "wait ".len();
+    let mut words = text[start..].split_whitespace();
+    let amount = match words.next()? {
+        "one" => 1,
+        number => number.parse().ok()?,
+    };
+    match words.next()? {
+        "minute" | "minutes" => Some(Duration::from_secs(amount * 60)),
+        "second" | "seconds" => Some(Duration::from_secs(amount)),
+        _ => None,
+    }
+}
+
+fn strip_tags(page: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for character in page.chars() {
+        match character {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Sends the answer, after refusing answers the history already rules out and sleeping through any
+/// wait that is left, and records what the server made of it.
+pub fn submit(
+    day: u8,
+    part: Part,
+    answer: &str,
+    base_url: &str,
+    session: &Session,
+    history: &mut History,
+    mut sleep: impl FnMut(Duration),
+) -> Result<Outcome, String> {
+    history.check(day, part, answer)?;
+    if let Some(wait) = history.wait_left(unix_now()) {
+        sleep(wait);
+    }
+
+    let url = format!("{base_url}/{YEAR}/day/{day}/answer");
+    let body = format!("level={part}&answer={}", encode(answer));
+    let response = send(&Request::post(&url, body).header("Cookie", &session.cookie()))?;
+    if response.status != 200 {
+        return Err(format!(
+            "{url} answered {}: {}",
+            response.status,
+            strip_tags(&response.body)
+        ));
+    }
+
+    let outcome = parse_outcome(&response.body)?;
+    history.record(Guess {
+        day,
+        part,
+        answer: answer.to_owned(),
+        verdict: outcome.verdict,
+        sent_at: unix_now(),
+        wait: outcome.wait,
+    })?;
+
+    Ok(outcome)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Percent-encodes everything but unreserved characters, for use in a form body.
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+fn guess(answer: &str, verdict: Verdict) -> Guess {
+    Guess {
+        day: 1,
+        part: Part::One,
+        answer: answer.to_owned(),
+        verdict,
+        sent_at: 1_000,
+        wait: Duration::from_secs(60),
+    }
+}
+
+#[test]
+fn test_parse_outcome() {
+    let page = |text: &str| format!("<main>\n<article><p>{text}</p></article>\n</main>");
+
+    assert_eq!(
+        parse_outcome(&page(
+            "That's the right answer!  You are <span>one gold star</span> closer."
+        )),
+        Ok(Outcome {
+            verdict: Verdict::Correct,
+            wait: Duration::ZERO
+        })
+    );
+    assert_eq!(
+        parse_outcome(&page(
+            "That's not the right answer; your answer is too high.  Please wait one minute before trying again."
+        )),
+        Ok(Outcome {
+            verdict: Verdict::TooHigh,
+            wait: Duration::from_secs(60)
+        })
+    );
+    assert_eq!(
+        parse_outcome(&page("That's not the right answer; your answer is too low. Please wait 5 minutes before trying again.")).map(|outcome| outcome.wait),
+        Ok(Duration::from_secs(300))
+    );
+    assert_eq!(
+        parse_outcome(&page(
+            "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 38s left to wait."
+        )),
+        Ok(Outcome {
+            verdict: Verdict::TooSoon,
+            wait: Duration::from_secs(98)
+        })
+    );
+    assert_eq!(
+        parse_outcome(&page(
+            "You don't seem to be solving the right level.  Did you already complete it?"
+        ))
+        .map(|outcome| outcome.verdict),
+        Ok(Verdict::AlreadySolved)
+    );
+    assert!(parse_outcome("<html>Log in</html>").is_err());
+}
+
+#[test]
+fn test_history_refuses_ruled_out_answers() {
+    let history = History {
+        path: PathBuf::new(),
+        guesses: vec![
+            guess("500", Verdict::TooHigh),
+            guess("100", Verdict::TooLow),
+            guess("abc", Verdict::Wrong),
+        ],
+    };
+
+    assert!(history.check(1, Part::One, "250").is_ok());
+    assert!(history.check(1, Part::One, "500").is_err());
+    assert!(history.check(1, Part::One, "700").is_err());
+    assert!(history.check(1, Part::One, "100").is_err());
+    assert!(history.check(1, Part::One, "-4").is_err());
+    assert!(history.check(1, Part::One, "abc").is_err());
+    assert!(history.check(1, Part::Two, "700").is_ok());
+    assert_eq!(history.wait_left(1_030), Some(Duration::from_secs(30)));
+    assert_eq!(history.wait_left(1_060), None);
+}
+
+#[test]
+fn test_history_round_trip() {
+    let path = std::env::temp_dir().join(format!("aoc-2025-history-{}", std::process::id()));
+    let mut history = History::read(&path).unwrap();
+    history.record(guess("42", Verdict::TooLow)).unwrap();
+    history.record(guess("43", Verdict::Correct)).unwrap();
+
+    let read_back = History::read(&path);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(read_back.unwrap().guesses, history.guesses);
+    assert!(parse_history("1 3 correct 0 0 42").is_err());
+    assert!(parse_history("1 1 maybe 0 0 42").is_err());
+}
+
+#[test]
+fn test_submit_to_mock_server() {
+    let path = std::env::temp_dir().join(format!("aoc-2025-submit-{}", std::process::id()));
+    let mut history = History::read(&path).unwrap();
+    let session = Session("secret".to_owned());
+    let (url, server) = crate::http::serve(vec![
+        "HTTP/1.1 200 OK\r\n\r\n<article><p>That's not the right answer; your answer is too low.  Please wait one minute before trying again.</p></article>".to_owned(),
+    ]);
+
+    let mut slept = Vec::new();
+    let outcome = submit(7, Part::Two, "1 2", &url, &session, &mut history, |wait| {
+        slept.push(wait)
+    });
+    let refused = submit(7, Part::Two, "1 2", &url, &session, &mut history, |_| {});
+    let requests = server.join().unwrap();
+    let read_back = History::read(&path);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(outcome.unwrap().verdict, Verdict::TooLow);
+    assert!(slept.is_empty());
+    assert!(refused.is_err());
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("POST /2025/day/7/answer HTTP/1.1\r\n"));
+    assert!(requests[0].contains("Cookie: session=secret\r\n"));
+    assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=1%202"));
+    assert_eq!(read_back.unwrap().guesses, history.guesses);
+}
@@ -0,0 +1,120 @@
+use crate::answers::Answers;
+use crate::cli::InputSelection;
+use crate::error::AocError;
+use crate::input::InputDirectory;
+use crate::output::{Record, Status};
+use crate::runner::{inputs, run_input};
+use crate::solution::{Day, Part};
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// Runs every part of the given days against every input on disk and checks the results against the
+/// expected answers. Expected answers without an input to check them against are reported as missing,
+/// and parts that run past the timeout as timed out.
+pub fn verify(
+    days: &[&dyn Day],
+    answers: &Answers,
+    timeout: Option<Duration>,
+    directory: &InputDirectory,
+) -> Vec<Record> {
+    let mut checked: HashSet<(u8, Part, String)> = HashSet::new();
+    let mut records: Vec<Record> = Vec::new();
+
+    for day in days {
+        let selection = InputSelection::ExampleAndPuzzle;
+        for input in inputs(day.number(), &Part::ALL, &selection, directory) {
+            let measurements = match run_input(*day, &input, timeout) {
+                Ok(measurements) => measurements,
+                Err(AocError::MissingInput(_)) => continue,
+                Err(err) => {
+                    records.extend(input.parts.iter().map(|part| {
+                        Record {
+                            day: day.number(),
+                            part: *part,
+                            input: input.name.clone(),
+                            answer: None,
+                            expected: answers
+                                .get(day.number(), *part, &input.name)
+                                .map(str::to_owned),
+                            parse_duration: None,
+                            solve_duration: None,
+                            status: Status::Fail,
+                            error: Some(err.to_string()),
+                            parse_memory: None,
+                            solve_memory: None,
+                        }
+                    }));
+                    continue;
+                }
+            };
+
+            for measurement in measurements {
+                records.push(Record::checked(&measurement, answers));
+
+                checked.insert((
+                    measurement.day,
+                    measurement.part,
+                    measurement.input.to_lowercase(),
+                ));
+            }
+        }
+    }
+
+    let verified_days: HashSet<u8> = days.iter().map(|day| day.number()).collect();
+    for ((day, part, input), expected) in answers.entries() {
+        if verified_days.contains(day) && !checked.contains(&(*day, *part, input.clone())) {
+            records.push(Record {
+                day: *day,
+                part: *part,
+                input: input.clone(),
+                answer: None,
+                expected: Some(expected.clone()),
+                parse_duration: None,
+                solve_duration: None,
+                status: Status::Missing,
+                error: None,
+                parse_memory: None,
+                solve_memory: None,
+            });
+        }
+    }
+
+    records
+}
+
+/// Prints a line per record and how many passed, failed, timed out or are missing.
+pub fn print_results(records: &[Record]) {
+    println!();
+    for record in records {
+        let label = format!("Day {}, Part {} {}", record.day, record.part, record.input);
+        let answer = record.answer.as_deref().unwrap_or_default();
+
+        let (status, message) = match (record.status, &record.expected, &record.error) {
+            (Status::Fail, _, Some(error)) => ("FAIL", format!("{label}: {error}")),
+            (Status::Timeout, _, _) => ("TIMEOUT", format!("{label}: ran past the timeout")),
+            (Status::Fail, Some(expected), None) => (
+                "FAIL",
+                format!("{label}: expected {expected}, got {answer}"),
+            ),
+            (Status::Missing, Some(expected), _) => (
+                "MISSING",
+                format!("{label}: no input to check {expected} against"),
+            ),
+            (Status::Missing, None, _) => (
+                "MISSING",
+                format!("{label}: {answer} has no expected answer"),
+            ),
+            _ => ("PASS", format!("{label}: {answer}")),
+        };
+        println!("{status:<7} {message}");
+    }
+
+    let count = |status: Status| records.iter().filter(|r| r.status == status).count();
+    println!(
+        "\n{} passed, {} failed, {} timed out, {} missing",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Timeout),
+        count(Status::Missing)
+    );
+}
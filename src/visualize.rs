@@ -0,0 +1,258 @@
+use crate::scoped::{self, Slot};
+use std::cell::RefCell;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, Write};
+use std::thread;
+use std::time::Duration;
+
+/// The terminal colors cells can be drawn in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    /// Whatever color the terminal draws text in.
+    #[default]
+    Default,
+    Gray,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    /// A 24-bit color, which not every terminal can draw.
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    fn ansi_code(&self) -> String {
+        match self {
+            Color::Default => "39".to_owned(),
+            Color::Gray => "90".to_owned(),
+            Color::Red => "31".to_owned(),
+            Color::Green => "32".to_owned(),
+            Color::Yellow => "33".to_owned(),
+            Color::Blue => "34".to_owned(),
+            Color::Magenta => "35".to_owned(),
+            Color::Cyan => "36".to_owned(),
+            Color::White => "97".to_owned(),
+            Color::Rgb(red, green, blue) => format!("38;2;{red};{green};{blue}"),
+        }
+    }
+
+    /// The color as red, green and blue, for drawing it in images.
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        match self {
+            Color::Default => (204, 204, 204),
+            Color::Gray => (128, 128, 128),
+            Color::Red => (220, 50, 47),
+            Color::Green => (133, 153, 0),
+            Color::Yellow => (181, 137, 0),
+            Color::Blue => (38, 139, 210),
+            Color::Magenta => (211, 54, 130),
+            Color::Cyan => (42, 161, 152),
+            Color::White => (238, 238, 238),
+            Color::Rgb(red, green, blue) => (*red, *green, *blue),
+        }
+    }
+
+    /// A color between `from` at 0 and `to` at 1.
+    pub fn blend(from: Color, to: Color, fraction: f64) -> Color {
+        let (from, to) = (from.rgb(), to.rgb());
+        let mix = |from: u8, to: u8| {
+            (from as f64 + (to as f64 - from as f64) * fraction.clamp(0.0, 1.0)).round() as u8
+        };
+
+        Color::Rgb(mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    pub symbol: char,
+    pub color: Color,
+}
+
+/// What a new frame is filled with.
+pub const EMPTY: Cell = Cell {
+    symbol: '.',
+    color: Color::Gray,
+};
+
+/// A snapshot of a grid while a solution works on it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub title: String,
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Frame {
+    /// A grid of empty cells.
+    pub fn new(title: impl Into<String>, width: usize, height: usize) -> Frame {
+        Frame {
+            title: title.into(),
+            width,
+            height,
+            cells: vec![EMPTY; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn cell(&self, x: usize, y: usize) -> Cell {
+        self.cells[y * self.width + x]
+    }
+
+    /// Draws a cell, cells outside of the grid are left out.
+    pub fn set(&mut self, x: usize, y: usize, symbol: char, color: Color) {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x] = Cell { symbol, color };
+        }
+    }
+
+    /// The title and the grid, with a color change wherever the color of the cells changes.
+    pub fn render(&self) -> String {
+        let mut output = format!("{}\n", self.title);
+
+        for row in self.cells.chunks(self.width.max(1)) {
+            let mut color = None;
+            for cell in row {
+                if color != Some(cell.color) {
+                    let _ = write!(output, "\x1b[{}m", cell.color.ansi_code());
+                    color = Some(cell.color);
+                }
+                output.push(cell.symbol);
+            }
+            output += "\x1b[0m\n";
+        }
+
+        output
+    }
+}
+
+thread_local! {
+    static RECORDING: Slot<Vec<Frame>> = const { RefCell::new(None) };
+}
+
+/// Whether frames emitted on this thread are kept. Solutions can check this before collecting
+/// anything that only a visualization needs.
+pub fn recording() -> bool {
+    scoped::is_set(&RECORDING)
+}
+
+/// Adds a frame to the [record] this thread is in. The frame is only drawn while recording.
+pub fn emit(frame: impl FnOnce() -> Frame) {
+    if recording() {
+        let frame = frame();
+        scoped::get(&RECORDING, |frames| frames.push(frame));
+    }
+}
+
+/// Runs `function` and returns the frames it emitted on this thread next to its result.
+pub fn record<T>(function: impl FnOnce() -> T) -> (T, Vec<Frame>) {
+    let (result, frames) = scoped::with(&RECORDING, Some(Vec::new()), function);
+
+    (result, frames.unwrap_or_default())
+}
+
+/// How frames follow each other when played.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pace {
+    /// Wait this long before drawing the next frame.
+    Delay(Duration),
+    /// Wait for Enter before drawing the next frame.
+    Step,
+}
+
+/// Draws the frames one after another over the same part of the terminal. When stepping through
+/// them, entering `q` stops before the last frame.
+pub fn play(
+    frames: &[Frame],
+    pace: Pace,
+    output: &mut impl Write,
+    keys: &mut impl BufRead,
+) -> io::Result<()> {
+    for (index, frame) in frames.iter().enumerate() {
+        // Moves the cursor home and clears the screen before every frame.
+        write!(output, "\x1b[H\x1b[2J{}", frame.render())?;
+
+        if index + 1 == frames.len() {
+            break;
+        }
+        match pace {
+            Pace::Delay(delay) => {
+                output.flush()?;
+                thread::sleep(delay);
+            }
+            Pace::Step => {
+                write!(
+                    output,
+                    "Frame {} of {}, Enter for the next one or q and Enter to stop ",
+                    index + 1,
+                    frames.len()
+                )?;
+                output.flush()?;
+
+                let mut line = String::new();
+                if keys.read_line(&mut line)? == 0 || line.trim() == "q" {
+                    break;
+                }
+            }
+        }
+    }
+
+    output.flush()
+}
+
+#[test]
+fn test_render_frame() {
+    let mut frame = Frame::new("Round 1", 3, 2);
+    frame.set(1, 0, '@', Color::Red);
+    frame.set(2, 0, '@', Color::Red);
+    frame.set(5, 5, '@', Color::Red);
+
+    assert_eq!(frame.cell(1, 0).symbol, '@');
+    assert_eq!(
+        frame.render(),
+        "Round 1\n\x1b[90m.\x1b[31m@@\x1b[0m\n\x1b[90m...\x1b[0m\n"
+    );
+}
+
+#[test]
+fn test_record_frames() {
+    emit(|| panic!("frames are only drawn while recording"));
+
+    let (answer, frames) = record(|| {
+        emit(|| Frame::new("first", 1, 1));
+        let (_, inner) = record(|| emit(|| Frame::new("inner", 1, 1)));
+        emit(|| Frame::new("second", 1, 1));
+        (recording(), inner.len())
+    });
+
+    let titles: Vec<&str> = frames.iter().map(|frame| frame.title.as_str()).collect();
+    assert_eq!(answer, (true, 1));
+    assert_eq!(titles, ["first", "second"]);
+    assert!(!recording());
+}
+
+#[test]
+fn test_step_through_frames() {
+    let frames: Vec<Frame> = (1..=3)
+        .map(|round| Frame::new(format!("Round {round}"), 1, 1))
+        .collect();
+    let mut output = Vec::new();
+
+    play(&frames, Pace::Step, &mut output, &mut "\nq\n".as_bytes()).unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("Round 1") && output.contains("Round 2"));
+    assert!(!output.contains("Round 3"));
+    assert!(output.contains("Frame 2 of 3"));
+}
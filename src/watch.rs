@@ -0,0 +1,395 @@
+use crate::cli::WatchOptions;
+use crate::input::InputDirectory;
+use crate::json::{self, Json};
+use crate::runner::{self, Measurement};
+use crate::solution::{Day, Part};
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::thread;
+use std::time::SystemTime;
+
+/// Points a restarted watch at the answers of the run before the restart, see [restart].
+const PREVIOUS_VARIABLE: &str = "AOC_WATCH_PREVIOUS";
+
+/// The answer of one part against one input, kept to compare the next run against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Answer {
+    pub part: Part,
+    pub input: String,
+    pub answer: String,
+}
+
+impl From<&Measurement> for Answer {
+    fn from(measurement: &Measurement) -> Self {
+        Answer {
+            part: measurement.part,
+            input: measurement.input.clone(),
+            answer: measurement
+                .answer
+                .clone()
+                .unwrap_or_else(|| "TIMEOUT".to_owned()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Change {
+    /// There was no answer for the part and input in the previous run.
+    New(String),
+    Same(String),
+    Changed {
+        before: String,
+        after: String,
+    },
+    /// The previous run had an answer, this one does not.
+    Gone(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct AnswerDiff {
+    pub part: Part,
+    pub input: String,
+    pub change: Change,
+}
+
+impl Display for AnswerDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Part {} {}: ", self.part, self.input)?;
+        match &self.change {
+            Change::New(answer) => write!(f, "{answer} (new)"),
+            Change::Same(answer) => write!(f, "{answer} (unchanged)"),
+            Change::Changed { before, after } => write!(f, "{before} -> {after}"),
+            Change::Gone(answer) => write!(f, "{answer} -> no answer"),
+        }
+    }
+}
+
+/// Compares the answers of a run to those of the run before it, in the order of the new run.
+pub fn diff(previous: &[Answer], current: &[Answer]) -> Vec<AnswerDiff> {
+    let matching =
+        |answer: &Answer, other: &Answer| answer.part == other.part && answer.input == other.input;
+
+    let mut diffs: Vec<AnswerDiff> = current
+        .iter()
+        .map(|answer| {
+            let change = match previous.iter().find(|before| matching(before, answer)) {
+                None => Change::New(answer.answer.clone()),
+                Some(before) if before.answer == answer.answer => {
+                    Change::Same(answer.answer.clone())
+                }
+                Some(before) => Change::Changed {
+                    before: before.answer.clone(),
+                    after: answer.answer.clone(),
+                },
+            };
+
+            AnswerDiff {
+                part: answer.part,
+                input: answer.input.clone(),
+                change,
+            }
+        })
+        .collect();
+
+    diffs.extend(
+        previous
+            .iter()
+            .filter(|before| !current.iter().any(|answer| matching(before, answer)))
+            .map(|before| AnswerDiff {
+                part: before.part,
+                input: before.input.clone(),
+                change: Change::Gone(before.answer.clone()),
+            }),
+    );
+
+    diffs
+}
+
+/// The files to watch and when each was last modified, `None` while a file does not exist.
+pub struct Watched {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl Watched {
+    pub fn new(paths: Vec<PathBuf>) -> Watched {
+        Watched {
+            files: paths
+                .into_iter()
+                .map(|path| {
+                    let modified = modified(&path);
+                    (path, modified)
+                })
+                .collect(),
+        }
+    }
+
+    /// Checks the files again and returns those that were modified, created or removed since the last
+    /// check.
+    pub fn changes(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+
+        for (path, last_modified) in &mut self.files {
+            let modified = modified(path);
+            if modified != *last_modified {
+                *last_modified = modified;
+                changed.push(path.clone());
+            }
+        }
+
+        changed
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Runs the day, and runs it again every time one of its inputs changes, printing how the answers
+/// differ from the run before. When `src/dayN.rs` changes the tool is rebuilt and started again with
+/// the same arguments.
+pub fn watch(day: &dyn Day, options: &WatchOptions, directory: &InputDirectory) -> ! {
+    let parts = runner::selected_parts(&options.run);
+    let source = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join(format!("day{}.rs", day.number()));
+    let mut paths: Vec<PathBuf> =
+        runner::inputs(day.number(), &parts, &options.run.inputs, directory)
+            .into_iter()
+            .map(|input| input.path)
+            .collect();
+    paths.extend(options.also_watch.iter().cloned());
+    paths.push(source.clone());
+
+    let mut watched = Watched::new(paths);
+    let mut previous: Vec<Answer> = take_previous();
+
+    loop {
+        for run in runner::run(&[day], &options.run, directory) {
+            eprint!("{}", run.log);
+            match run.result {
+                Ok(measurements) => {
+                    let answers: Vec<Answer> = measurements.iter().map(Answer::from).collect();
+                    for answer_diff in diff(&previous, &answers) {
+                        println!("{answer_diff}");
+                    }
+                    previous = answers;
+                }
+                Err(err) => eprintln!("Day {}: {err}", run.day),
+            }
+        }
+
+        println!(
+            "\nWatching {} files for changes, press Ctrl-C to stop",
+            watched.files.len()
+        );
+        let changed = loop {
+            thread::sleep(options.interval);
+            let changed = watched.changes();
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+        for path in &changed {
+            println!("{} changed", path.display());
+        }
+        if changed.contains(&source) {
+            restart(&previous);
+        }
+    }
+}
+
+/// Rebuilds the tool and replaces this process with the new build, running it with the same
+/// arguments. The answers so far are handed over in a file, so the new build shows how the code
+/// change affected them. When the build fails the old build keeps watching, so the code can be fixed.
+fn restart(previous: &[Answer]) {
+    let profile: &[&str] = if cfg!(debug_assertions) {
+        &[]
+    } else {
+        &["--release"]
+    };
+
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+
+    println!("Rebuilding");
+    match Command::new("cargo")
+        .args(["build", "--quiet"])
+        .args(profile)
+        .arg("--manifest-path")
+        .arg(&manifest)
+        .status()
+    {
+        Ok(status) if status.success() => {}
+        Ok(_) => {
+            eprintln!("The build failed, still running the previous build");
+            return;
+        }
+        Err(err) => {
+            eprintln!("Failed to run cargo: {err}");
+            return;
+        }
+    }
+
+    let mut command = Command::new("cargo");
+    command
+        .args(["run", "--quiet"])
+        .args(profile)
+        .arg("--manifest-path")
+        .arg(&manifest)
+        .arg("--")
+        .args(env::args_os().skip(1));
+
+    let path = env::temp_dir().join(format!("aoc-2025-watch-{}.json", process::id()));
+    match fs::write(&path, answers_to_json(previous).to_string()) {
+        Ok(()) => {
+            command.env(PREVIOUS_VARIABLE, &path);
+        }
+        Err(err) => eprintln!(
+            "Failed to write {}, the answers start over: {err}",
+            path.display()
+        ),
+    }
+    exec(command);
+}
+
+/// The answers a [restart] handed over, or none when this is not a restarted watch.
+fn take_previous() -> Vec<Answer> {
+    let Some(path) = env::var_os(PREVIOUS_VARIABLE) else {
+        return Vec::new();
+    };
+    let text = fs::read_to_string(&path);
+    let _ = fs::remove_file(&path);
+
+    text.ok()
+        .and_then(|text| json::parse(&text).ok())
+        .and_then(|json| answers_from_json(&json))
+        .unwrap_or_default()
+}
+
+fn answers_to_json(answers: &[Answer]) -> Json {
+    Json::Array(
+        answers
+            .iter()
+            .map(|answer| {
+                Json::Object(vec![
+                    ("part".to_owned(), Json::String(answer.part.to_string())),
+                    ("input".to_owned(), Json::String(answer.input.clone())),
+                    ("answer".to_owned(), Json::String(answer.answer.clone())),
+                ])
+            })
+            .collect(),
+    )
+}
+
+fn answers_from_json(json: &Json) -> Option<Vec<Answer>> {
+    json.as_array()?
+        .iter()
+        .map(|answer| {
+            let field = |key| answer.get(key).and_then(Json::as_str);
+            let part = field("part")?;
+
+            Some(Answer {
+                part: *Part::ALL.iter().find(|known| known.to_string() == part)?,
+                input: field("input")?.to_owned(),
+                answer: field("answer")?.to_owned(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(unix)]
+fn exec(mut command: Command) {
+    use std::os::unix::process::CommandExt;
+
+    let err = command.exec();
+    eprintln!("Failed to start the new build: {err}");
+}
+
+/// Without `exec` the new build runs as a child, and this process exits once it does.
+#[cfg(not(unix))]
+fn exec(mut command: Command) {
+    match command.status() {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(err) => eprintln!("Failed to start the new build: {err}"),
+    }
+}
+
+#[cfg(test)]
+fn answer(part: Part, input: &str, answer: &str) -> Answer {
+    Answer {
+        part,
+        input: input.to_owned(),
+        answer: answer.to_owned(),
+    }
+}
+
+#[test]
+fn test_diff_answers() {
+    let previous = vec![
+        answer(Part::One, "Example", "3"),
+        answer(Part::One, "Puzzle", "1034"),
+        answer(Part::Two, "Example", "6"),
+    ];
+    let current = vec![
+        answer(Part::One, "Example", "3"),
+        answer(Part::One, "Puzzle", "1041"),
+        answer(Part::Two, "Puzzle", "6166"),
+    ];
+
+    let lines: Vec<String> = diff(&previous, &current)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    assert_eq!(
+        lines,
+        [
+            "Part 1 Example: 3 (unchanged)",
+            "Part 1 Puzzle: 1034 -> 1041",
+            "Part 2 Puzzle: 6166 (new)",
+            "Part 2 Example: 6 -> no answer",
+        ]
+    );
+}
+
+#[test]
+fn test_watched_changes() {
+    let path = std::env::temp_dir().join(format!("aoc-2025-watch-{}", std::process::id()));
+    let mut watched = Watched::new(vec![path.clone()]);
+
+    let unchanged = watched.changes();
+    fs::write(&path, "1").unwrap();
+    let created = watched.changes();
+    fs::File::options()
+        .write(true)
+        .open(&path)
+        .and_then(|file| file.set_modified(SystemTime::UNIX_EPOCH))
+        .unwrap();
+    let modified = watched.changes();
+    let again = watched.changes();
+    fs::remove_file(&path).unwrap();
+    let removed = watched.changes();
+
+    assert!(unchanged.is_empty());
+    assert_eq!(created, std::slice::from_ref(&path));
+    assert_eq!(modified, std::slice::from_ref(&path));
+    assert!(again.is_empty());
+    assert_eq!(removed, [path]);
+}
+
+#[test]
+fn test_answers_survive_a_restart() {
+    let answers = vec![
+        answer(Part::One, "Example", "3"),
+        answer(Part::Two, "puzzle \"input\".txt", "TIMEOUT"),
+    ];
+
+    let json = json::parse(&answers_to_json(&answers).to_string()).unwrap();
+
+    assert_eq!(answers_from_json(&json), Some(answers));
+    assert_eq!(answers_from_json(&Json::Null), None);
+}
@@ -0,0 +1,150 @@
+//! Runs every registered day against every input on disk and checks the answers against
+//! `answers.txt`, with a test case per day, part and input. New days, inputs and answers are picked
+//! up without writing any test code.
+//!
+//! Cases are only known at run time, so this file has its own `main` instead of `#[test]` functions.
+//! Like the standard harness it takes a filter, `cargo test --test examples -- day_7`, and `--list`.
+
+use aoc_2025::answers::{Answers, read_answers_if_present};
+use aoc_2025::input::{InputDirectory, InputKind};
+use aoc_2025::log::{self, Level};
+use aoc_2025::registry;
+use aoc_2025::runner::{Input, run_input};
+use aoc_2025::solution::{Day, Part};
+use std::env;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::process::ExitCode;
+
+struct Case {
+    name: String,
+    day: &'static dyn Day,
+    part: Part,
+    input: Input,
+}
+
+enum Outcome {
+    Passed,
+    Failed(String),
+    Ignored(&'static str),
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let list = args.iter().any(|arg| arg == "--list");
+    let filter = args.iter().find(|arg| !arg.starts_with('-'));
+
+    let directory = InputDirectory::default();
+    let answers = match read_answers_if_present(&directory.answers_path()) {
+        Ok(answers) => answers,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let cases: Vec<Case> = discover(&directory)
+        .into_iter()
+        .filter(|case| filter.is_none_or(|filter| case.name.contains(filter.as_str())))
+        .collect();
+
+    if list {
+        for case in &cases {
+            println!("{}: test", case.name);
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    log::set_level(Level::Quiet);
+    println!("\nrunning {} tests", cases.len());
+
+    let mut failures: Vec<(String, String)> = Vec::new();
+    let (mut passed, mut ignored) = (0, 0);
+    for case in &cases {
+        match check(case, &answers) {
+            Outcome::Passed => {
+                passed += 1;
+                println!("test {} ... ok", case.name);
+            }
+            Outcome::Failed(message) => {
+                println!("test {} ... FAILED", case.name);
+                failures.push((case.name.clone(), message));
+            }
+            Outcome::Ignored(reason) => {
+                ignored += 1;
+                println!("test {} ... ignored, {reason}", case.name);
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        println!("\nfailures:");
+        for (name, message) in &failures {
+            println!("\n---- {name} ----\n{message}");
+        }
+    }
+
+    let result = if failures.is_empty() { "ok" } else { "FAILED" };
+    println!(
+        "\ntest result: {result}. {passed} passed; {} failed; {ignored} ignored\n",
+        failures.len()
+    );
+
+    if failures.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// A case for both parts of every day against its example and puzzle input.
+fn discover(directory: &InputDirectory) -> Vec<Case> {
+    let mut cases = Vec::new();
+
+    for day in registry::DAYS {
+        for part in Part::ALL {
+            for (name, kind) in [
+                ("Example", InputKind::Example(part)),
+                ("Puzzle", InputKind::Puzzle),
+            ] {
+                cases.push(Case {
+                    name: format!("day_{}_part_{part}_{}", day.number(), name.to_lowercase()),
+                    day: *day,
+                    part,
+                    input: Input {
+                        name: name.to_owned(),
+                        path: directory.path(day.number(), kind),
+                        parts: vec![part],
+                        example: name == "Example",
+                    },
+                });
+            }
+        }
+    }
+
+    cases
+}
+
+fn check(case: &Case, answers: &Answers) -> Outcome {
+    if !case.input.path.exists() {
+        return Outcome::Ignored("no input file");
+    }
+    let Some(expected) = answers.get(case.day.number(), case.part, &case.input.name) else {
+        return Outcome::Ignored("no expected answer");
+    };
+
+    match catch_unwind(AssertUnwindSafe(|| run_input(case.day, &case.input, None))) {
+        Ok(Ok(measurements)) => match measurements.first() {
+            Some(measurement) => match &measurement.answer {
+                Some(answer) if answer == expected => Outcome::Passed,
+                Some(answer) => Outcome::Failed(format!(
+                    "expected {expected}, got {answer} for {}",
+                    case.input.path.display()
+                )),
+                None => Outcome::Failed("the part timed out".to_owned()),
+            },
+            None => Outcome::Failed("the part did not run".to_owned()),
+        },
+        Ok(Err(error)) => Outcome::Failed(error.to_string()),
+        Err(_) => Outcome::Failed(format!("panicked on {}", case.input.path.display())),
+    }
+}